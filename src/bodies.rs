@@ -1,24 +1,43 @@
 use crate::vectors::Vector2;
+use rand::Rng;
 use sdl2::pixels::Color;
 use sdl2::rect::Rect;
-use sdl2::render::{Texture, WindowCanvas};
+use sdl2::render::{Canvas, RenderTarget, Texture};
+use serde::{Deserialize, Serialize};
 
+#[derive(Serialize, Deserialize)]
 pub struct Body {
     pub mass: f64,
     pub position: Vector2,
     pub velocity: Vector2,
     pub velocity_norm: Vector2,
+    #[serde(with = "rect_serde")]
     pub rect: Rect,
+    #[serde(with = "rect_serde")]
     pub collision_rect: Rect,
 }
 
+mod rect_serde {
+    use sdl2::rect::Rect;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(rect: &Rect, serializer: S) -> Result<S::Ok, S::Error> {
+        (rect.x(), rect.y(), rect.width(), rect.height()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Rect, D::Error> {
+        let (x, y, w, h) = <(i32, i32, u32, u32)>::deserialize(deserializer)?;
+        Ok(Rect::new(x, y, w, h))
+    }
+}
+
 impl Body {
     pub fn get_size(mass: f64) -> u32 {
         (4.5 * mass.sqrt()) as u32
     }
 
-    pub fn new(mass: f64, position: Vector2) -> Self {
-        let vel = Vector2::random_in_radius(1.0);
+    pub fn new<R: Rng>(mass: f64, position: Vector2, rng: &mut R) -> Self {
+        let vel = Vector2::random_in_radius(1.0, rng);
         let (x, y): (f64, f64) = position.get_components();
         let (vx, vy): (f64, f64) = vel.get_components();
         let size = Body::get_size(mass);
@@ -60,7 +79,7 @@ impl Body {
         self.rescale();
     }
 
-    pub fn draw(&self, canvas: &mut WindowCanvas, texture: &Texture, debug: bool) {
+    pub fn draw<T: RenderTarget>(&self, canvas: &mut Canvas<T>, texture: &Texture, debug: bool) {
         let angle = self.velocity.angle();
         canvas
             .copy_ex(
@@ -87,6 +106,8 @@ impl Body {
 
 pub trait Vision {
     fn in_sight(&self, target: Vector2) -> f64;
+
+    fn sight_radius(&self) -> f64;
 }
 
 pub trait Position {
@@ -95,4 +116,4 @@ pub trait Position {
     fn vel(&self) -> Vector2 {
         Vector2::default()
     }
-}
\ No newline at end of file
+}
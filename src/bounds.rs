@@ -0,0 +1,187 @@
+use crate::vectors::Vector2;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BoundsMode {
+    None,
+    Wrap,
+    Bounce,
+    Contain,
+}
+
+#[derive(Clone, Copy)]
+pub struct Bounds {
+    pub mode: BoundsMode,
+    pub width: f64,
+    pub height: f64,
+    pub margin: f64,
+}
+
+fn nearest_offset(d: f64, size: f64) -> f64 {
+    d - size * (d / size).round()
+}
+
+fn reflect(p: &mut f64, v: &mut f64, size: f64) {
+    if *p < 0.0 {
+        *p = -*p;
+        *v = v.abs();
+    } else if *p > size {
+        *p = 2.0 * size - *p;
+        *v = -v.abs();
+    }
+    *p = p.clamp(0.0, size);
+}
+
+impl Bounds {
+    pub fn wrap_size(&self) -> Option<(f64, f64)> {
+        (self.mode == BoundsMode::Wrap).then_some((self.width, self.height))
+    }
+
+    pub fn center(&self) -> Vector2 {
+        Vector2::new(self.width / 2.0, self.height / 2.0)
+    }
+
+    pub fn contains(&self, p: Vector2) -> bool {
+        let (x, y) = p.get_components();
+        self.mode == BoundsMode::None
+            || ((0.0..=self.width).contains(&x) && (0.0..=self.height).contains(&y))
+    }
+
+    pub fn delta(&self, from: Vector2, to: Vector2) -> Vector2 {
+        let d = to - from;
+        if self.mode != BoundsMode::Wrap {
+            return d;
+        }
+
+        let (dx, dy) = d.get_components();
+        Vector2::new(
+            nearest_offset(dx, self.width),
+            nearest_offset(dy, self.height),
+        )
+    }
+
+    pub fn image(&self, from: Vector2, to: Vector2) -> Vector2 {
+        from + self.delta(from, to)
+    }
+
+    pub fn wrap(&self, p: Vector2) -> Vector2 {
+        let (x, y) = p.get_components();
+        Vector2::new(x.rem_euclid(self.width), y.rem_euclid(self.height))
+    }
+
+    pub fn confine(&self, position: &mut Vector2, velocity: &mut Vector2) {
+        match self.mode {
+            BoundsMode::Wrap => *position = self.wrap(*position),
+            BoundsMode::Bounce => {
+                let ((mut x, mut y), (mut vx, mut vy)) =
+                    (position.get_components(), velocity.get_components());
+                reflect(&mut x, &mut vx, self.width);
+                reflect(&mut y, &mut vy, self.height);
+                *position = Vector2::new(x, y);
+                *velocity = Vector2::new(vx, vy);
+            }
+            BoundsMode::None | BoundsMode::Contain => {}
+        }
+    }
+
+    pub fn containment(&self, position: Vector2, velocity: Vector2, speed: f64) -> Option<Vector2> {
+        if self.mode != BoundsMode::Contain {
+            return None;
+        }
+
+        let (x, y) = position.get_components();
+        let (mut dx, mut dy) = velocity.get_components();
+        let mut outside = false;
+        if x < self.margin {
+            (dx, outside) = (speed, true);
+        } else if x > self.width - self.margin {
+            (dx, outside) = (-speed, true);
+        }
+        if y < self.margin {
+            (dy, outside) = (speed, true);
+        } else if y > self.height - self.margin {
+            (dy, outside) = (-speed, true);
+        }
+
+        outside.then(|| Vector2::new(dx, dy) - velocity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds(mode: BoundsMode) -> Bounds {
+        Bounds {
+            mode,
+            width: 100.0,
+            height: 50.0,
+            margin: 10.0,
+        }
+    }
+
+    #[test]
+    fn wrap_maps_edges_into_range() {
+        let b = bounds(BoundsMode::Wrap);
+        assert_eq!(
+            b.wrap(Vector2::new(100.0, -50.0)).get_components(),
+            (0.0, 0.0)
+        );
+        assert_eq!(
+            b.wrap(Vector2::new(-1.0, 51.0)).get_components(),
+            (99.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn wrap_delta_takes_the_shortest_way_around() {
+        let b = bounds(BoundsMode::Wrap);
+        let d = b.delta(Vector2::new(95.0, 2.0), Vector2::new(5.0, 48.0));
+        assert_eq!(d.get_components(), (10.0, -4.0));
+
+        let d = bounds(BoundsMode::Contain).delta(Vector2::new(95.0, 2.0), Vector2::new(5.0, 48.0));
+        assert_eq!(d.get_components(), (-90.0, 46.0));
+    }
+
+    #[test]
+    fn contains_is_inclusive_at_the_edges() {
+        let b = bounds(BoundsMode::Contain);
+        assert!(b.contains(Vector2::new(0.0, 0.0)));
+        assert!(b.contains(Vector2::new(100.0, 50.0)));
+        assert!(!b.contains(Vector2::new(100.1, 25.0)));
+        assert!(!b.contains(Vector2::new(50.0, -0.1)));
+        assert!(bounds(BoundsMode::None).contains(Vector2::new(-500.0, 500.0)));
+    }
+
+    #[test]
+    fn containment_pushes_back_only_past_the_margin() {
+        let b = bounds(BoundsMode::Contain);
+        let v = Vector2::new(1.0, 0.0);
+        assert!(b.containment(Vector2::new(50.0, 25.0), v, 2.0).is_none());
+        assert!(b.containment(Vector2::new(90.0, 40.0), v, 2.0).is_none());
+
+        let force = b.containment(Vector2::new(95.0, 25.0), v, 2.0).unwrap();
+        assert_eq!(force.get_components(), (-3.0, 0.0));
+        let force = b.containment(Vector2::new(5.0, 45.0), v, 2.0).unwrap();
+        assert_eq!(force.get_components(), (1.0, -2.0));
+
+        assert!(bounds(BoundsMode::Wrap)
+            .containment(Vector2::new(95.0, 25.0), v, 2.0)
+            .is_none());
+    }
+
+    #[test]
+    fn bounce_reflects_position_and_velocity() {
+        let b = bounds(BoundsMode::Bounce);
+        let mut p = Vector2::new(103.0, -2.0);
+        let mut v = Vector2::new(1.0, -1.0);
+        b.confine(&mut p, &mut v);
+        assert_eq!(p.get_components(), (97.0, 2.0));
+        assert_eq!(v.get_components(), (-1.0, 1.0));
+
+        let mut p = Vector2::new(100.0, 50.0);
+        b.confine(&mut p, &mut v);
+        assert_eq!(p.get_components(), (100.0, 50.0));
+    }
+}
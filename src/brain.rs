@@ -0,0 +1,87 @@
+use crate::genome::gaussian;
+use crate::vectors::Vector2;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+
+pub const BRAIN_INPUTS: usize = 8;
+const BRAIN_HIDDEN: usize = 8;
+const BRAIN_OUTPUTS: usize = 2;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Brain {
+    hidden: Vec<f64>,
+    output: Vec<f64>,
+}
+
+fn layer(weights: &[f64], inputs: &[f64], outputs: &mut [f64]) {
+    let stride = inputs.len() + 1;
+    for (o, output) in outputs.iter_mut().enumerate() {
+        let row = &weights[o * stride..(o + 1) * stride];
+        let sum: f64 = row[0] + row[1..].iter().zip(inputs).map(|(w, x)| w * x).sum::<f64>();
+        *output = sum.tanh();
+    }
+}
+
+impl Brain {
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
+        let hidden = (0..(BRAIN_INPUTS + 1) * BRAIN_HIDDEN)
+            .map(|_| gaussian(rng) * 0.5)
+            .collect();
+        let output = (0..(BRAIN_HIDDEN + 1) * BRAIN_OUTPUTS)
+            .map(|_| gaussian(rng) * 0.5)
+            .collect();
+
+        Self { hidden, output }
+    }
+
+    pub fn think(&self, inputs: &[f64; BRAIN_INPUTS]) -> Vector2 {
+        let mut hidden = [0.0; BRAIN_HIDDEN];
+        let mut output = [0.0; BRAIN_OUTPUTS];
+        layer(&self.hidden, inputs, &mut hidden);
+        layer(&self.output, &hidden, &mut output);
+
+        Vector2::new(output[0], output[1])
+    }
+
+    pub fn mutate<R: Rng>(&self, rate: f64, sigma: f64, rng: &mut R) -> Self {
+        let mut mutate_weight = |w: &f64| {
+            if rng.gen::<f64>() >= rate {
+                return *w;
+            }
+
+            w + sigma * gaussian(rng)
+        };
+
+        Self {
+            hidden: self.hidden.iter().map(&mut mutate_weight).collect(),
+            output: self.output.iter().map(&mut mutate_weight).collect(),
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.hidden.len() == (BRAIN_INPUTS + 1) * BRAIN_HIDDEN
+            && self.output.len() == (BRAIN_HIDDEN + 1) * BRAIN_OUTPUTS
+    }
+}
+
+pub fn save_all<'a, I: Iterator<Item = &'a Brain>>(path: &str, brains: I) -> Result<(), String> {
+    let brains: Vec<&Brain> = brains.collect();
+    let file = File::create(path).map_err(|e| format!("{path}: {e}"))?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &brains).map_err(|e| format!("{path}: {e}"))?;
+    writer.flush().map_err(|e| format!("{path}: {e}"))
+}
+
+pub fn load_all(path: &str) -> Result<Vec<Brain>, String> {
+    let file = File::open(path).map_err(|e| format!("{path}: {e}"))?;
+    let brains: Vec<Brain> =
+        serde_json::from_reader(BufReader::new(file)).map_err(|e| format!("{path}: {e}"))?;
+
+    if brains.is_empty() || !brains.iter().all(Brain::is_valid) {
+        return Err(format!("{path}: arquivo de cérebros vazio ou incompatível"));
+    }
+
+    Ok(brains)
+}
@@ -0,0 +1,49 @@
+use crate::vectors::Vector2;
+use serde::{Deserialize, Serialize};
+
+const MIN_ZOOM: f64 = 0.1;
+const MAX_ZOOM: f64 = 8.0;
+pub const ZOOM_STEP: f64 = 1.25;
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Camera {
+    pub offset: Vector2,
+    pub zoom: f64,
+}
+
+impl Camera {
+    pub fn new(offset: Vector2) -> Self {
+        Self { offset, zoom: 1.0 }
+    }
+
+    pub fn fit(screen: (u32, u32), world: (f64, f64)) -> Self {
+        let (sw, sh) = (screen.0 as f64, screen.1 as f64);
+        let zoom = (sw / world.0).min(sh / world.1).clamp(MIN_ZOOM, MAX_ZOOM);
+        Self {
+            offset: Vector2::new(
+                sw / 2.0 / zoom - world.0 / 2.0,
+                sh / 2.0 / zoom - world.1 / 2.0,
+            ),
+            zoom,
+        }
+    }
+
+    pub fn world_to_screen(&self, point: Vector2) -> Vector2 {
+        (point + self.offset) * self.zoom
+    }
+
+    pub fn screen_to_world(&self, point: Vector2) -> Vector2 {
+        point * (1.0 / self.zoom) - self.offset
+    }
+
+    pub fn pan(&mut self, screen_dx: f64, screen_dy: f64) {
+        self.offset
+            .offset(screen_dx / self.zoom, screen_dy / self.zoom);
+    }
+
+    pub fn zoom_at(&mut self, screen_point: Vector2, factor: f64) {
+        let anchor = self.screen_to_world(screen_point);
+        self.zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        self.offset = screen_point * (1.0 / self.zoom) - anchor;
+    }
+}
@@ -0,0 +1,118 @@
+use crate::params::{SimParam, DEFAULT_CONFIG_PATH};
+
+pub const USAGE: &str = "\
+Uso: aquarium-rs [opções] [--<parâmetro> <valor>...]
+
+Opções:
+  --config <arquivo>     arquivo de configuração TOML (padrão: aquarium.toml)
+  --seed <n>             semente do gerador aleatório
+  --ticks <n>            encerra a simulação após n ticks
+  --width <px>           largura da janela ou dos quadros (padrão: tela cheia)
+  --height <px>          altura da janela ou dos quadros (padrão: tela cheia)
+  --headless             simula sem janela nem formulário (requer --ticks)
+  --no-dialog            pula o formulário de parâmetros
+  --sweep <arquivo>      executa a varredura de parâmetros descrita no arquivo TOML
+  --help                 mostra esta mensagem
+
+Qualquer campo de SimParam pode ser passado como --<campo> <valor> e
+qualquer campo de espécie como --<espécie>.<campo> <valor>, por exemplo
+--preys.pop 20 ou --predators.vis-d=1200 (--pr_pop e --pd_vis_d também
+valem para as espécies padrão).";
+
+pub struct CliArgs {
+    pub config_path: String,
+    pub explicit_config: bool,
+    pub seed: Option<i64>,
+    pub tick_limit: Option<u64>,
+    pub window_size: Option<(u32, u32)>,
+    pub headless: bool,
+    pub skip_dialog: bool,
+    pub help: bool,
+    pub sweep_path: Option<String>,
+    overrides: Vec<(String, String)>,
+}
+
+fn parse_value<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("valor inválido para --{name}: {value}"))
+}
+
+impl CliArgs {
+    pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Self, String> {
+        let mut cli = Self {
+            config_path: String::from(DEFAULT_CONFIG_PATH),
+            explicit_config: false,
+            seed: None,
+            tick_limit: None,
+            window_size: None,
+            headless: false,
+            skip_dialog: false,
+            help: false,
+            sweep_path: None,
+            overrides: vec![],
+        };
+        let mut width = None;
+        let mut height = None;
+
+        while let Some(arg) = args.next() {
+            let Some(flag) = arg.strip_prefix("--") else {
+                return Err(format!("argumento inesperado: {arg}"));
+            };
+            let (name, inline_value) = match flag.split_once('=') {
+                Some((name, value)) => (name.replace('-', "_"), Some(value.to_string())),
+                None => (flag.replace('-', "_"), None),
+            };
+
+            match name.as_str() {
+                "headless" => cli.headless = true,
+                "no_dialog" => cli.skip_dialog = true,
+                "help" => cli.help = true,
+                _ => {
+                    let value = match inline_value {
+                        Some(value) => value,
+                        None => args.next().ok_or(format!("faltando valor para --{name}"))?,
+                    };
+
+                    match name.as_str() {
+                        "config" => {
+                            cli.config_path = value;
+                            cli.explicit_config = true;
+                        }
+                        "seed" => cli.seed = Some(parse_value(&name, &value)?),
+                        "ticks" => cli.tick_limit = Some(parse_value(&name, &value)?),
+                        "width" => width = Some(parse_value(&name, &value)?),
+                        "height" => height = Some(parse_value(&name, &value)?),
+                        "sweep" => cli.sweep_path = Some(value),
+                        _ => cli.overrides.push((name, value)),
+                    }
+                }
+            }
+        }
+
+        cli.window_size = match (width, height) {
+            (None, None) => None,
+            (Some(w), Some(h)) => Some((w, h)),
+            _ => return Err(String::from("--width e --height devem ser usados juntos")),
+        };
+
+        Ok(cli)
+    }
+
+    pub fn load_parameters(&self) -> Result<SimParam, String> {
+        let mut parameters = if self.explicit_config {
+            SimParam::load(&self.config_path)?
+        } else {
+            SimParam::load_or_default(&self.config_path)?
+        };
+
+        for (name, value) in &self.overrides {
+            parameters.set(name, value)?;
+        }
+        if let Some(seed) = self.seed {
+            parameters.seed = seed;
+        }
+
+        Ok(parameters)
+    }
+}
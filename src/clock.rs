@@ -0,0 +1,105 @@
+use std::time::{Duration, Instant};
+
+const MAX_TICKS_PER_FRAME: u32 = 8;
+const SPEEDS: [f64; 4] = [0.25, 1.0, 4.0, 16.0];
+const DEFAULT_SPEED: usize = 1;
+
+pub struct SimClock {
+    tick: Duration,
+    accumulator: Duration,
+    last: Instant,
+    speed: usize,
+    paused: bool,
+}
+
+impl SimClock {
+    pub fn new(tick_rate: u32) -> Self {
+        Self {
+            tick: Duration::from_secs(1) / tick_rate,
+            accumulator: Duration::ZERO,
+            last: Instant::now(),
+            speed: DEFAULT_SPEED,
+            paused: false,
+        }
+    }
+
+    pub fn speed(&self) -> f64 {
+        SPEEDS[self.speed]
+    }
+
+    pub fn faster(&mut self) {
+        self.speed = (self.speed + 1).min(SPEEDS.len() - 1);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed = self.speed.saturating_sub(1);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    pub fn pending_ticks(&mut self) -> u32 {
+        let now = Instant::now();
+        let elapsed = now - self.last;
+        self.last = now;
+
+        if self.paused {
+            self.accumulator = Duration::ZERO;
+            return 0;
+        }
+        self.accumulator += elapsed.mul_f64(self.speed());
+
+        let mut ticks = 0;
+        while self.accumulator >= self.tick {
+            self.accumulator -= self.tick;
+            ticks += 1;
+        }
+
+        let max_ticks = (MAX_TICKS_PER_FRAME as f64 * self.speed().max(1.0)) as u32;
+        if ticks > max_ticks {
+            self.accumulator = Duration::ZERO;
+            ticks = max_ticks;
+        }
+
+        ticks
+    }
+}
+
+pub struct RateCounter {
+    count: u32,
+    window_start: Instant,
+    rate: f64,
+}
+
+impl RateCounter {
+    pub fn new() -> Self {
+        Self {
+            count: 0,
+            window_start: Instant::now(),
+            rate: 0.0,
+        }
+    }
+
+    pub fn count(&mut self, n: u32) -> bool {
+        self.count += n;
+
+        let elapsed = self.window_start.elapsed();
+        if elapsed < Duration::from_secs(1) {
+            return false;
+        }
+
+        self.rate = self.count as f64 / elapsed.as_secs_f64();
+        self.count = 0;
+        self.window_start = Instant::now();
+        true
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+}
@@ -1,25 +1,39 @@
 use crate::bodies::{Body, Position, Vision};
+use crate::bounds::Bounds;
+use crate::brain::BRAIN_INPUTS;
+use crate::camera::Camera;
+use crate::genome::Genome;
+use crate::obstacles::{self, Obstacle};
+use crate::steering::{Steering, SteeringMode};
 use crate::vectors::Vector2;
+use rand::Rng;
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
 use sdl2::render;
-use sdl2::render::WindowCanvas;
+use sdl2::render::{Canvas, RenderTarget};
+use serde::{Deserialize, Serialize};
 
+const ENERGY_PER_MASS: f64 = 100.0;
+const CONE_SEGMENTS: u32 = 16;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
 enum FishBehaviour {
-    STILL,
-    WANDERING,
-    SEEKING,
-    ARRIVING,
-    FLEEING,
+    Still,
+    Wandering,
+    Seeking,
+    Arriving,
+    Fleeing,
+    Thinking,
 }
 
+#[derive(Serialize, Deserialize)]
 struct Flock {
     separation_vec: Vector2,
-    separation_w: f64,
-
     cohesion_vec: Vector2,
-    cohesion_w: f64,
-
     alignment_vec: Vector2,
-    alignment_w: f64,
+    neighbors: u32,
+    force: Vector2,
 }
 
 impl Flock {
@@ -28,6 +42,7 @@ impl Flock {
         self.separation_vec = zero_vec;
         self.cohesion_vec = zero_vec;
         self.alignment_vec = zero_vec;
+        self.neighbors = 0;
     }
 
     fn add(
@@ -41,64 +56,144 @@ impl Flock {
         self.cohesion_vec += neighbor_position;
 
         self.alignment_vec += neighbor_velocity;
+
+        self.neighbors += 1;
     }
 }
 
+#[derive(Serialize, Deserialize)]
 struct FishDesireVectors {
     wander_vector: Vector2,
     flocking: Flock,
 }
 
+impl FishDesireVectors {
+    fn new() -> Self {
+        Self {
+            wander_vector: Vector2::new(0.0, 0.0),
+            flocking: Flock {
+                separation_vec: Vector2::new(0.0, 0.0),
+                cohesion_vec: Vector2::new(0.0, 0.0),
+                alignment_vec: Vector2::new(0.0, 0.0),
+                neighbors: 0,
+                force: Vector2::new(0.0, 0.0),
+            },
+        }
+    }
+}
+
+pub struct Reproduction {
+    pub growth: f64,
+    pub energy: f64,
+    pub litter: u32,
+    pub gestation: u32,
+    pub cost: f64,
+    pub mutation_rate: f64,
+    pub mutation_sigma: f64,
+    pub brain_sigma: f64,
+}
+
+#[derive(Serialize, Deserialize)]
 pub struct Fish {
     body: Body,
-    pub health: u32,
+    energy: f64,
     behaviour: FishBehaviour,
+    genome: Genome,
+    generation: u32,
     vision_range: f64,
-    vision_depth: f64,
     desires: FishDesireVectors,
-    max_force: f64,
-    peak_speed: f64,
-    default_speed: f64,
     current_speed: f64,
+    gestation: u32,
+    #[serde(skip)]
+    steering: Steering,
 }
 
 impl Fish {
-    pub fn new(
-        pos: Vector2,
-        mass: f64,
-        vision_angle: f64,
-        vision_depth: f64,
-        peak_speed: f64,
-    ) -> Self {
+    pub fn new<R: Rng>(pos: Vector2, genome: Genome, rng: &mut R) -> Self {
+        Self::born(pos, genome.mass, genome, 0, rng)
+    }
+
+    fn born<R: Rng>(pos: Vector2, mass: f64, genome: Genome, generation: u32, rng: &mut R) -> Self {
         Self {
-            body: Body::new(mass, pos),
-            health: 100,
-            behaviour: FishBehaviour::STILL,
-            vision_range: vision_angle.cos(),
-            vision_depth,
-            max_force: 10.0,
-            peak_speed,
-            default_speed: peak_speed / 3.0,
+            body: Body::new(mass, pos, rng),
+            energy: mass * ENERGY_PER_MASS / 2.0,
+            behaviour: FishBehaviour::Still,
+            vision_range: genome.vision_angle.cos(),
+            genome,
+            generation,
             current_speed: 0.0,
-            desires: FishDesireVectors {
-                wander_vector: Vector2::new(0.0, 0.0),
-                flocking: Flock {
-                    separation_vec: Vector2::new(0.0, 0.0),
-                    separation_w: 1.0,
-                    cohesion_vec: Vector2::new(0.0, 0.0),
-                    cohesion_w: 1.0,
-                    alignment_vec: Vector2::new(0.0, 0.0),
-                    alignment_w: 1.0,
-                },
-            },
+            gestation: 0,
+            desires: FishDesireVectors::new(),
+            steering: Steering::default(),
         }
     }
 
-    fn steer(&mut self, steer_force: Vector2, clamp_speed: f64) {
-        if (steer_force - self.body.position).length_sqr() < 1.0 {
-            return;
+    pub fn genome(&self) -> &Genome {
+        &self.genome
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    fn default_speed(&self) -> f64 {
+        self.genome.peak_speed / 3.0
+    }
+
+    pub fn reproduce<R: Rng>(&mut self, params: &Reproduction, rng: &mut R) -> Vec<Self> {
+        if self.gestation > 0 {
+            self.gestation -= 1;
+            return vec![];
         }
 
+        if params.litter == 0
+            || self.body.mass < self.genome.mass * params.growth
+            || self.energy < self.max_energy() * params.energy
+        {
+            return vec![];
+        }
+
+        let litter_mass = self.body.mass * params.cost;
+        let child_mass = litter_mass / params.litter as f64;
+        let spread = Body::get_size(self.body.mass) as f64;
+        let litter: Vec<Self> = (0..params.litter)
+            .map(|_| {
+                let pos = self.body.position + Vector2::random_in_radius(spread, rng);
+                let genome = self.genome.mutate(
+                    params.mutation_rate,
+                    params.mutation_sigma,
+                    params.brain_sigma,
+                    rng,
+                );
+                Self::born(pos, child_mass, genome, self.generation + 1, rng)
+            })
+            .collect();
+
+        self.body.shrink(litter_mass);
+        self.energy =
+            (self.energy - litter.iter().map(|c| c.energy).sum::<f64>()).min(self.max_energy());
+        self.gestation = params.gestation;
+
+        litter
+    }
+
+    pub fn max_energy(&self) -> f64 {
+        self.body.mass * ENERGY_PER_MASS
+    }
+
+    pub fn eat(&mut self, food_mass: f64, assimilation: f64) {
+        self.energy = (self.energy + food_mass * ENERGY_PER_MASS).min(self.max_energy());
+        self.body.grow(food_mass * assimilation);
+    }
+
+    pub fn metabolise(&mut self, basal_cost: f64, swim_cost: f64) -> bool {
+        let speed = self.body.velocity.length();
+        self.energy -= self.body.mass * (basal_cost + swim_cost * speed);
+
+        self.energy > 0.0
+    }
+
+    fn steer(&mut self, steer_force: Vector2, clamp_speed: f64) {
         let clamp_increment = clamp_speed / 10.0;
         if self.current_speed < clamp_speed {
             self.current_speed += clamp_increment;
@@ -106,124 +201,313 @@ impl Fish {
             self.current_speed -= clamp_increment;
         }
 
-        self.body.velocity += (steer_force % self.max_force) * (1.0 / self.body.mass);
+        if steer_force.length_sqr() >= 1.0 {
+            self.body.velocity += (steer_force % self.genome.max_force) * (1.0 / self.body.mass);
+        }
         self.body.velocity %= self.current_speed;
         self.body.position += self.body.velocity;
-        self.body.velocity_norm = self.body.velocity.norm();
+        if self.body.velocity.length_sqr() > 0.0 {
+            self.body.velocity_norm = self.body.velocity.norm();
+        }
     }
 
-    pub fn update_rects(&mut self, offset: Vector2, _scale: f64) {
-        let rect_size = Vector2::new(self.body.rect.w as f64, self.body.rect.h as f64);
-        let collision_rect_size = Vector2::new(
-            self.body.collision_rect.w as f64,
-            self.body.collision_rect.h as f64,
-        );
+    fn push_behaviour(
+        &mut self,
+        behaviour: FishBehaviour,
+        force: Vector2,
+        weight: f64,
+        clamp_speed: f64,
+    ) {
+        if self.steering.tag() {
+            self.behaviour = behaviour;
+        }
+        self.steering.add(force, weight);
+        self.steering.request_speed(clamp_speed);
+    }
 
-        let (new_rx, new_ry) = ((self.body.position - (rect_size * 0.5)) + offset).get_components();
-        let (new_collision_x, new_collision_y) = (self.body.position
-            + (self.body.velocity_norm * (self.body.rect.width() as f64 / 4.0))
-            - (collision_rect_size * 0.5)
-            + offset)
-            .get_components();
-        self.body.rect.reposition((new_rx as i32, new_ry as i32));
+    pub fn apply_steering(&mut self, mode: SteeringMode) {
+        let clamp_speed = self.steering.clamp_speed();
+        let steer_force = self.steering.resolve(mode, self.genome.max_force);
+        self.steer(steer_force, clamp_speed);
+    }
+
+    pub fn update_rects(&mut self, offset: Vector2, scale: f64) {
+        let size = Body::get_size(self.body.mass) as f64;
+        let rect_size = ((size * scale) as u32).max(1);
+        let collision_rect_size = (rect_size / 2).max(1);
+        self.body.rect.resize(rect_size, rect_size);
         self.body
             .collision_rect
-            .reposition((new_collision_x as i32, new_collision_y as i32));
+            .resize(collision_rect_size, collision_rect_size);
+
+        let (new_rx, new_ry) = ((self.body.position + offset) * scale).get_components();
+        let (new_collision_x, new_collision_y) =
+            ((self.body.position + (self.body.velocity_norm * (size / 4.0)) + offset) * scale)
+                .get_components();
+        self.body.rect.center_on((new_rx as i32, new_ry as i32));
+        self.body
+            .collision_rect
+            .center_on((new_collision_x as i32, new_collision_y as i32));
     }
 
-    pub fn wander(&mut self) {
+    pub fn wander<R: Rng>(&mut self, weight: f64, rng: &mut R) {
         match self.behaviour {
-            FishBehaviour::WANDERING => {
-                self.desires.wander_vector += Vector2::random_in_radius(5.0);
+            FishBehaviour::Wandering => {
+                self.desires.wander_vector += Vector2::random_in_radius(5.0, rng);
             }
             _ => {
-                self.behaviour = FishBehaviour::WANDERING;
+                self.behaviour = FishBehaviour::Wandering;
                 self.desires.wander_vector = self.body.velocity.mag(10.0);
             }
         }
 
-        self.steer(
-            self.desires.wander_vector.mag(self.max_force) - self.body.velocity,
-            self.default_speed,
+        self.push_behaviour(
+            FishBehaviour::Wandering,
+            self.desires.wander_vector.mag(self.genome.max_force) - self.body.velocity,
+            weight,
+            self.default_speed(),
         );
     }
 
-    pub fn seek(&mut self, target: Vector2) {
-        let desired_velocity = (target - self.body.position) % self.max_force;
+    pub fn seek(&mut self, target: Vector2, weight: f64) {
+        let desired_velocity = (target - self.body.position) % self.genome.max_force;
 
-        self.behaviour = FishBehaviour::SEEKING;
-        self.steer(desired_velocity - self.body.velocity, self.peak_speed);
+        self.push_behaviour(
+            FishBehaviour::Seeking,
+            desired_velocity - self.body.velocity,
+            weight,
+            self.genome.peak_speed,
+        );
     }
 
-    pub fn arrive(&mut self, target: Vector2) {
+    pub fn arrive(&mut self, target: Vector2, weight: f64) {
         let to_target = target - self.body.position;
         let clipped_speed = {
-            let a = self.peak_speed * to_target.length() / 100.0;
-            if a < self.peak_speed {
+            let a = self.genome.peak_speed * to_target.length() / 100.0;
+            if a < self.genome.peak_speed {
                 a
             } else {
-                self.peak_speed
+                self.genome.peak_speed
             }
         };
         let desired_velocity = to_target.mag(clipped_speed);
 
-        self.behaviour = FishBehaviour::ARRIVING;
-        self.steer(desired_velocity - self.body.velocity, clipped_speed);
+        self.push_behaviour(
+            FishBehaviour::Arriving,
+            desired_velocity - self.body.velocity,
+            weight,
+            clipped_speed,
+        );
     }
 
-    pub fn flee(&mut self, target: Vector2) {
-        let desired_velocity = (self.body.position - target) % self.max_force;
+    pub fn flee(&mut self, target: Vector2, weight: f64) {
+        let desired_velocity = (self.body.position - target) % self.genome.max_force;
 
-        self.behaviour = FishBehaviour::FLEEING;
-        self.steer(desired_velocity - self.body.velocity, self.peak_speed);
+        self.push_behaviour(
+            FishBehaviour::Fleeing,
+            desired_velocity - self.body.velocity,
+            weight,
+            self.genome.peak_speed,
+        );
     }
 
-    pub fn pursuit(&mut self, target_pos: Vector2, target_vel: Vector2) {
+    pub fn pursuit(&mut self, target_pos: Vector2, target_vel: Vector2, weight: f64) {
         let scale = (target_pos - self.body.position).length() * 0.1;
         let desired_pos = target_pos + target_vel.mag(scale);
 
-        self.seek(desired_pos);
+        self.seek(desired_pos, weight);
+    }
+
+    pub fn has_brain(&self) -> bool {
+        self.genome.brain.is_some()
+    }
+
+    pub fn think(
+        &mut self,
+        plant: Option<Vector2>,
+        predator: Option<Vector2>,
+        prey: Option<Vector2>,
+        weight: f64,
+    ) {
+        let Some(brain) = &self.genome.brain else {
+            return;
+        };
+
+        let mut inputs = [0.0; BRAIN_INPUTS];
+        for (slot, target) in [plant, predator, prey].into_iter().enumerate() {
+            if let Some(target) = target {
+                let (x, y) = ((target - self.body.position) * (1.0 / self.genome.vision_depth))
+                    .get_components();
+                inputs[slot * 2] = x;
+                inputs[slot * 2 + 1] = y;
+            }
+        }
+        let (vx, vy) = (self.body.velocity * (1.0 / self.genome.peak_speed)).get_components();
+        inputs[6] = vx;
+        inputs[7] = vy;
+
+        let steer_force = brain.think(&inputs) * self.genome.max_force;
+
+        self.push_behaviour(
+            FishBehaviour::Thinking,
+            steer_force,
+            weight,
+            self.genome.peak_speed,
+        );
     }
 
-    pub fn evade(&mut self, target_pos: Vector2, target_vel: Vector2) {
+    pub fn evade(&mut self, target_pos: Vector2, target_vel: Vector2, weight: f64) {
         let desired_pos = target_pos + target_vel;
 
-        self.flee(desired_pos);
+        self.flee(desired_pos, weight);
     }
 
-    pub fn ponder_flock(&mut self, neighbor: Body) {
+    pub fn ponder_flock(&mut self, neighbor_pos: Vector2, neighbor_vel: Vector2) {
         self.desires.flocking.add(
-            self.body.position - neighbor.position,
-            neighbor.position,
-            neighbor.velocity_norm,
+            self.body.position - neighbor_pos,
+            neighbor_pos,
+            neighbor_vel,
         )
     }
 
     pub fn compute_flock(&mut self) {
-        let separation =
-            self.desires.flocking.separation_vec.norm() * self.desires.flocking.separation_w;
-        let cohesion = self.desires.flocking.cohesion_vec.norm() * self.desires.flocking.cohesion_w;
-        let alignment =
-            self.desires.flocking.alignment_vec.norm() * self.desires.flocking.alignment_w;
+        let flocking = &self.desires.flocking;
+        if flocking.neighbors == 0 {
+            self.desires.flocking.force = Vector2::new(0.0, 0.0);
+            return;
+        }
 
-        let flock_vec = separation + cohesion + alignment;
+        let unit = |v: Vector2| if v.length_sqr() > 0.0 { v.norm() } else { v };
+        let inv_count = 1.0 / flocking.neighbors as f64;
+        let separation = unit(flocking.separation_vec) * self.genome.separation_w;
+        let cohesion =
+            unit(flocking.cohesion_vec * inv_count - self.body.position) * self.genome.cohesion_w;
+        let alignment =
+            unit(flocking.alignment_vec * inv_count - self.body.velocity) * self.genome.alignment_w;
 
-        self.steer(flock_vec, self.peak_speed); //verificar se é melhor subtrair a velocidade atual.
+        self.desires.flocking.force = (separation + cohesion + alignment) * self.genome.max_force;
         self.desires.flocking.clear();
     }
 
+    pub fn avoid(&mut self, obstacles: &[Obstacle], feeler_length: f64, weight: f64) {
+        for reach in [0.0, 0.5, 1.0] {
+            let feeler = self.body.position + self.body.velocity_norm * (feeler_length * reach);
+            if let Some(exit) = obstacles::escape_all(obstacles, feeler) {
+                let away = exit - feeler;
+                if away.length_sqr() > 0.0 {
+                    self.steering.add(away.mag(self.genome.max_force), weight);
+                }
+                return;
+            }
+        }
+    }
+
+    pub fn contain(&mut self, bounds: &Bounds, weight: f64) {
+        let ahead = self.body.position + self.body.velocity * 10.0;
+        if let Some(force) = bounds.containment(ahead, self.body.velocity, self.genome.peak_speed) {
+            self.steering.add(force % self.genome.max_force, weight);
+        }
+    }
+
+    pub fn confine(&mut self, bounds: &Bounds) {
+        bounds.confine(&mut self.body.position, &mut self.body.velocity);
+    }
+
+    pub fn collide(&mut self, obstacles: &[Obstacle]) {
+        if let Some(exit) = obstacles::escape_all(obstacles, self.body.position) {
+            self.body.position = exit;
+        }
+    }
+
+    pub fn flock(&mut self, weight: f64) {
+        self.steering.add(self.desires.flocking.force, weight);
+    }
+
     pub fn pos(&self) -> Vector2 {
         self.body.position
     }
 
-    pub fn draw(
+    pub fn mass(&self) -> f64 {
+        self.body.mass
+    }
+
+    pub fn hit(&self, x: i32, y: i32) -> bool {
+        self.body.rect.contains_point((x, y))
+    }
+
+    fn behaviour_color(&self) -> Color {
+        match self.behaviour {
+            FishBehaviour::Still => Color::GRAY,
+            FishBehaviour::Wandering => Color::WHITE,
+            FishBehaviour::Seeking => Color::RED,
+            FishBehaviour::Arriving => Color::GREEN,
+            FishBehaviour::Fleeing => Color::YELLOW,
+            FishBehaviour::Thinking => Color::MAGENTA,
+        }
+    }
+
+    fn draw_debug<T: RenderTarget>(&self, canvas: &mut Canvas<T>, camera: &Camera) {
+        let to_screen = |p: Vector2| {
+            let (x, y) = camera.world_to_screen(p).get_components();
+            Point::new(x as i32, y as i32)
+        };
+        let origin = to_screen(self.body.position);
+        let heading = self.body.velocity_norm.angle().to_radians();
+        let half_angle = self.vision_range.acos();
+        let depth = self.genome.vision_depth;
+
+        canvas.set_draw_color(self.behaviour_color());
+        let cone: Vec<Point> = (0..=CONE_SEGMENTS)
+            .map(|s| {
+                let angle =
+                    heading - half_angle + 2.0 * half_angle * s as f64 / CONE_SEGMENTS as f64;
+                to_screen(self.body.position + Vector2::new(angle.cos(), angle.sin()) * depth)
+            })
+            .collect();
+        canvas.draw_line(origin, cone[0]).unwrap();
+        canvas.draw_lines(&cone[..]).unwrap();
+        canvas.draw_line(origin, cone[cone.len() - 1]).unwrap();
+
+        canvas.set_draw_color(Color::CYAN);
+        canvas
+            .draw_line(
+                origin,
+                to_screen(self.body.position + self.body.velocity * 10.0),
+            )
+            .unwrap();
+
+        canvas.set_draw_color(Color::RGB(255, 128, 0));
+        canvas
+            .draw_line(
+                origin,
+                to_screen(self.body.position + self.steering.last_force() * 10.0),
+            )
+            .unwrap();
+
+        if let FishBehaviour::Wandering = self.behaviour {
+            let target = to_screen(self.body.position + self.desires.wander_vector);
+            canvas.set_draw_color(Color::WHITE);
+            canvas
+                .draw_rect(Rect::new(target.x() - 2, target.y() - 2, 5, 5))
+                .unwrap();
+        }
+    }
+
+    pub fn draw<T: RenderTarget>(
         &mut self,
-        canvas: &mut WindowCanvas,
+        canvas: &mut Canvas<T>,
         texture: &render::Texture,
-        display_offset: Vector2,
+        camera: &Camera,
+        debug: bool,
     ) {
-        self.update_rects(display_offset, 1.0);
-        self.body.draw(canvas, texture, false);
+        self.update_rects(camera.offset, camera.zoom);
+        self.body.draw(canvas, texture, debug);
+
+        if debug {
+            let color = canvas.draw_color();
+            self.draw_debug(canvas, camera);
+            canvas.set_draw_color(color);
+        }
     }
 }
 
@@ -232,7 +516,7 @@ impl Vision for Fish {
         let to_target = target - self.body.position;
 
         let sqr_dist = to_target.length_sqr();
-        if sqr_dist > (self.vision_depth * self.vision_depth) {
+        if sqr_dist > (self.genome.vision_depth * self.genome.vision_depth) {
             return -1.0;
         }
 
@@ -242,6 +526,10 @@ impl Vision for Fish {
 
         sqr_dist
     }
+
+    fn sight_radius(&self) -> f64 {
+        self.genome.vision_depth
+    }
 }
 
 impl Position for Fish {
@@ -254,6 +542,7 @@ impl Position for Fish {
     }
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct Plant {
     body: Body,
     spreading_radius: f64,
@@ -262,62 +551,85 @@ pub struct Plant {
 }
 
 impl Plant {
-    pub fn new(pos: Vector2, mass: f64) -> Self {
+    pub fn new<R: Rng>(pos: Vector2, mass: f64, spreading_radius: f64, rng: &mut R) -> Self {
         Self {
-            body: Body::new(mass, pos),
-            spreading_radius: 250.0,
+            body: Body::new(mass, pos, rng),
+            spreading_radius,
             health: (mass * 15.0) as u32,
             division_mass: 40.0,
         }
     }
 
-    fn spread(&mut self) -> (Self, Self) {
+    fn spread<R: Rng>(&mut self, rng: &mut R) -> (Self, Self) {
         self.health -= 20;
         self.body.shrink(self.division_mass / 1.5);
         let rootlings = (
             Self::new(
-                self.body.position + Vector2::random_in_radius(self.spreading_radius),
+                self.body.position + Vector2::random_in_radius(self.spreading_radius, rng),
                 self.division_mass / 3.0,
+                self.spreading_radius,
+                rng,
             ),
             Self::new(
-                self.body.position + Vector2::random_in_radius(self.spreading_radius),
+                self.body.position + Vector2::random_in_radius(self.spreading_radius, rng),
                 self.division_mass / 3.0,
+                self.spreading_radius,
+                rng,
             ),
         );
 
         rootlings
     }
 
-    pub fn grow(&mut self) -> Option<(Self, Self)> {
+    pub fn grow<R: Rng>(&mut self, rng: &mut R) -> Option<(Self, Self)> {
         self.health += 2;
         self.body.grow(self.division_mass / 20.0);
 
         if self.body.mass > self.division_mass {
-            return Some(self.spread());
+            return Some(self.spread(rng));
         }
 
         None
     }
 
-    pub fn update_rects(&mut self, offset: Vector2, _scale: f64) {
-        let rect_pos = Vector2::new(self.body.rect.w as f64, self.body.rect.h as f64);
-        let _collision_rect = Vector2::new(
-            self.body.collision_rect.w as f64,
-            self.body.collision_rect.h as f64,
-        );
-        let (new_rx, new_ry) = ((self.body.position - (rect_pos * 0.5)) + offset).get_components();
-        self.body.rect.reposition((new_rx as i32, new_ry as i32));
+    pub fn mass(&self) -> f64 {
+        self.body.mass
+    }
+
+    pub fn hit(&self, x: i32, y: i32) -> bool {
+        self.body.rect.contains_point((x, y))
+    }
+
+    pub fn confine(&mut self, bounds: &Bounds) -> bool {
+        if bounds.wrap_size().is_some() {
+            self.body.position = bounds.wrap(self.body.position);
+        }
+
+        bounds.contains(self.body.position)
+    }
+
+    pub fn bitten(&mut self, bite: f64) {
+        self.body.shrink(bite);
+    }
+
+    pub fn update_rects(&mut self, offset: Vector2, scale: f64) {
+        let rect_size = ((Body::get_size(self.body.mass) as f64 * scale) as u32).max(1);
+        self.body.rect.resize(rect_size, rect_size);
+
+        let (new_rx, new_ry) = ((self.body.position + offset) * scale).get_components();
+        self.body.rect.center_on((new_rx as i32, new_ry as i32));
         self.body.collision_rect = self.body.rect;
     }
 
-    pub fn draw(
+    pub fn draw<T: RenderTarget>(
         &mut self,
-        canvas: &mut WindowCanvas,
+        canvas: &mut Canvas<T>,
         texture: &render::Texture,
-        display_offset: Vector2,
+        camera: &Camera,
+        debug: bool,
     ) {
-        self.update_rects(display_offset, 1.0);
-        self.body.draw(canvas, texture, false);
+        self.update_rects(camera.offset, camera.zoom);
+        self.body.draw(canvas, texture, debug);
     }
 }
 
@@ -332,10 +644,61 @@ impl Vision for Plant {
         let to_target = target - self.body.position;
 
         let sqr_dist = to_target.length_sqr();
-        if sqr_dist > (self.body.rect.width() * self.body.rect.width()) as f64 {
+        let reach = self.sight_radius();
+        if sqr_dist > reach * reach {
             return -1.0;
         }
 
         sqr_dist
     }
-}
\ No newline at end of file
+    fn sight_radius(&self) -> f64 {
+        Body::get_size(self.body.mass) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::species::Species;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn fish_heading_east() -> Fish {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let mut fish = Fish::new(Vector2::default(), Species::default().genome(), &mut rng);
+        fish.body.velocity = Vector2::new(1.0, 0.0);
+        fish.body.velocity_norm = Vector2::new(1.0, 0.0);
+        fish
+    }
+
+    #[test]
+    fn avoid_pushes_back_from_an_obstacle_on_the_feeler() {
+        let mut fish = fish_heading_east();
+        let rock = [Obstacle::Circle {
+            center: [40.0, 0.0],
+            radius: 20.0,
+        }];
+        fish.avoid(&rock, 50.0, 1.0);
+
+        let (x, y) = fish
+            .steering
+            .resolve(SteeringMode::Weighted, fish.genome.max_force)
+            .get_components();
+        assert_eq!((x, y), (-fish.genome.max_force, 0.0));
+    }
+
+    #[test]
+    fn avoid_ignores_obstacles_beyond_the_feeler() {
+        let mut fish = fish_heading_east();
+        let rock = [Obstacle::Circle {
+            center: [100.0, 0.0],
+            radius: 20.0,
+        }];
+        fish.avoid(&rock, 50.0, 1.0);
+
+        let force = fish
+            .steering
+            .resolve(SteeringMode::Weighted, fish.genome.max_force);
+        assert_eq!(force.get_components(), (0.0, 0.0));
+    }
+}
@@ -0,0 +1,63 @@
+use crate::camera::Camera;
+use crate::scene;
+use crate::world::World;
+use sdl2::image::SaveSurface;
+use sdl2::pixels::{Color, PixelFormatEnum};
+use sdl2::render::{Canvas, Texture, TextureCreator};
+use sdl2::surface::{Surface, SurfaceContext};
+use std::fs;
+
+pub fn create_canvas(width: u32, height: u32) -> Result<Canvas<Surface<'static>>, String> {
+    Surface::new(width, height, PixelFormatEnum::RGB24)?.into_canvas()
+}
+
+pub struct FrameWriter<'a> {
+    dir: String,
+    interval: u64,
+    camera: Camera,
+    textures: Vec<Texture<'a>>,
+}
+
+impl<'a> FrameWriter<'a> {
+    pub fn create(
+        tex_creator: &'a TextureCreator<SurfaceContext<'static>>,
+        dir: &str,
+        interval: u64,
+        camera: Camera,
+        world: &World,
+    ) -> Result<Self, String> {
+        fs::create_dir_all(dir).map_err(|e| format!("{dir}: {e}"))?;
+
+        Ok(Self {
+            dir: dir.to_string(),
+            interval: interval.max(1),
+            camera,
+            textures: scene::load_textures(tex_creator, world)?,
+        })
+    }
+
+    pub fn record(
+        &mut self,
+        canvas: &mut Canvas<Surface<'static>>,
+        world: &mut World,
+    ) -> Result<(), String> {
+        if !world.ticks().is_multiple_of(self.interval) {
+            return Ok(());
+        }
+
+        canvas.set_draw_color(Color::BLACK);
+        canvas.clear();
+        scene::render(canvas, world, &self.textures, &self.camera, false);
+        canvas.present();
+
+        let path = format!("{}/frame_{:08}.png", self.dir, world.ticks());
+        let surface = canvas.surface_mut();
+        let (width, height) = surface.size();
+        let (pitch, format) = (surface.pitch(), surface.pixel_format_enum());
+        surface.with_lock_mut(|pixels| {
+            Surface::from_data(pixels, width, height, pitch, format)?
+                .save(&path)
+                .map_err(|e| format!("{path}: {e}"))
+        })
+    }
+}
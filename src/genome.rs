@@ -0,0 +1,79 @@
+use crate::brain::Brain;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Genome {
+    pub mass: f64,
+    pub vision_angle: f64,
+    pub vision_depth: f64,
+    pub peak_speed: f64,
+    pub max_force: f64,
+    pub separation_w: f64,
+    pub cohesion_w: f64,
+    pub alignment_w: f64,
+    #[serde(default)]
+    pub brain: Option<Brain>,
+}
+
+pub const TRAIT_NAMES: [&str; 8] = [
+    "mass",
+    "vision_angle",
+    "vision_depth",
+    "peak_speed",
+    "max_force",
+    "separation_w",
+    "cohesion_w",
+    "alignment_w",
+];
+
+pub fn gaussian<R: Rng>(rng: &mut R) -> f64 {
+    let u1 = 1.0 - rng.gen::<f64>();
+    let u2 = rng.gen::<f64>();
+
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+impl Genome {
+    pub fn traits(&self) -> [f64; 8] {
+        [
+            self.mass,
+            self.vision_angle,
+            self.vision_depth,
+            self.peak_speed,
+            self.max_force,
+            self.separation_w,
+            self.cohesion_w,
+            self.alignment_w,
+        ]
+    }
+
+    pub fn mutate<R: Rng>(&self, rate: f64, sigma: f64, brain_sigma: f64, rng: &mut R) -> Self {
+        let mut mutate_trait = |value: f64| {
+            if rng.gen::<f64>() >= rate {
+                return value;
+            }
+
+            (value * (1.0 + sigma * gaussian(rng))).max(0.0)
+        };
+
+        let mut genome = Self {
+            mass: mutate_trait(self.mass).max(0.1),
+            vision_angle: mutate_trait(self.vision_angle),
+            vision_depth: mutate_trait(self.vision_depth),
+            peak_speed: mutate_trait(self.peak_speed),
+            max_force: mutate_trait(self.max_force),
+            separation_w: mutate_trait(self.separation_w),
+            cohesion_w: mutate_trait(self.cohesion_w),
+            alignment_w: mutate_trait(self.alignment_w),
+            brain: None,
+        };
+        genome.brain = self
+            .brain
+            .as_ref()
+            .map(|brain| brain.mutate(rate, brain_sigma, rng));
+
+        genome
+    }
+}
@@ -0,0 +1,118 @@
+use crate::species::SpeciesRegistry;
+use crate::text::{draw_text, text_size};
+use crate::world::{World, TICK_RATE};
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{BlendMode, WindowCanvas};
+use std::collections::VecDeque;
+
+const SAMPLES_PER_SECOND: u64 = 4;
+const GRAPH_WIDTH: u32 = 360;
+const GRAPH_HEIGHT: u32 = 140;
+const GRAPH_MARGIN: i32 = 10;
+const GRAPH_PADDING: i32 = 8;
+const GRAPH_PANEL: Color = Color::RGBA(10, 20, 35, 200);
+const PLANT_COLOR: Color = Color::RGB(80, 200, 90);
+const FISH_COLORS: [Color; 5] = [
+    Color::RGB(250, 170, 60),
+    Color::RGB(230, 70, 70),
+    Color::RGB(90, 150, 250),
+    Color::RGB(200, 110, 230),
+    Color::RGB(240, 230, 90),
+];
+
+pub struct PopulationGraph {
+    series: Vec<(String, Color)>,
+    samples: VecDeque<Vec<usize>>,
+    capacity: usize,
+}
+
+impl PopulationGraph {
+    pub fn new(seconds: u32, species: &SpeciesRegistry) -> Self {
+        let capacity = (seconds as usize * SAMPLES_PER_SECOND as usize).max(2);
+        let mut series = vec![(String::from("Plantas"), PLANT_COLOR)];
+        series.extend(
+            species
+                .iter()
+                .zip(FISH_COLORS.iter().cycle())
+                .map(|(s, color)| (s.label().to_string(), *color)),
+        );
+
+        Self {
+            series,
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn record(&mut self, world: &World) {
+        if !world
+            .ticks()
+            .is_multiple_of(TICK_RATE as u64 / SAMPLES_PER_SECOND)
+        {
+            return;
+        }
+
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        let mut sample = vec![world.plants.len()];
+        sample.extend(world.fishes.iter().map(|fishes| fishes.len()));
+        self.samples.push_back(sample);
+    }
+
+    pub fn draw(&self, canvas: &mut WindowCanvas) -> Result<(), String> {
+        let (screen_w, screen_h) = canvas.output_size()?;
+        let panel = Rect::new(
+            screen_w as i32 - GRAPH_MARGIN - GRAPH_WIDTH as i32,
+            screen_h as i32 - GRAPH_MARGIN - GRAPH_HEIGHT as i32,
+            GRAPH_WIDTH,
+            GRAPH_HEIGHT,
+        );
+
+        let blend = canvas.blend_mode();
+        canvas.set_blend_mode(BlendMode::Blend);
+        canvas.set_draw_color(GRAPH_PANEL);
+        canvas.fill_rect(panel)?;
+        canvas.set_blend_mode(blend);
+
+        let legend_height = text_size("", 1).1 as i32 + 4;
+        let plot_x = panel.x() + GRAPH_PADDING;
+        let plot_y = panel.y() + GRAPH_PADDING + legend_height;
+        let plot_w = panel.width() as i32 - 2 * GRAPH_PADDING;
+        let plot_h = panel.height() as i32 - 2 * GRAPH_PADDING - legend_height;
+        let step = plot_w as f64 / (self.capacity - 1) as f64;
+
+        let mut legend_x = plot_x;
+        for (series, (name, color)) in self.series.iter().enumerate() {
+            let peak = self.samples.iter().map(|s| s[series]).max().unwrap_or(0);
+            let label = format!("{name} ({peak})");
+            draw_text(
+                canvas,
+                legend_x,
+                panel.y() + GRAPH_PADDING,
+                1,
+                *color,
+                &label,
+            );
+            legend_x += text_size(&label, 1).0 as i32 + 12;
+
+            let scale = plot_h as f64 / peak.max(1) as f64;
+            let points: Vec<Point> = self
+                .samples
+                .iter()
+                .enumerate()
+                .map(|(i, s)| {
+                    Point::new(
+                        plot_x + (i as f64 * step) as i32,
+                        plot_y + plot_h - (s[series] as f64 * scale) as i32,
+                    )
+                })
+                .collect();
+            canvas.set_draw_color(*color);
+            canvas.draw_lines(&points[..])?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use crate::text::{draw_text, text_size};
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{BlendMode, WindowCanvas};
+
+const HUD_SCALE: u32 = 2;
+const HUD_MARGIN: i32 = 10;
+const HUD_PADDING: i32 = 8;
+const HUD_PANEL: Color = Color::RGBA(10, 20, 35, 200);
+const HUD_TEXT: Color = Color::RGB(220, 230, 240);
+
+pub struct HudInfo {
+    pub plants: usize,
+    pub fishes: Vec<(String, usize)>,
+    pub sim_seconds: f64,
+    pub tick_rate: f64,
+    pub fps: f64,
+    pub seed: i64,
+    pub paused: bool,
+    pub speed: f64,
+}
+
+pub fn draw_hud(canvas: &mut WindowCanvas, info: &HudInfo) {
+    let mut lines = vec![format!("Plantas: {}", info.plants)];
+    lines.extend(
+        info.fishes
+            .iter()
+            .map(|(name, count)| format!("{name}: {count}")),
+    );
+    lines.extend([
+        format!("Tempo: {:.1} s", info.sim_seconds),
+        format!("Ticks/s: {:.1}", info.tick_rate),
+        format!("FPS: {:.1}", info.fps),
+        format!("Semente: {}", info.seed),
+        if info.paused {
+            String::from("Pausado")
+        } else {
+            format!("Rodando ({}x)", info.speed)
+        },
+    ]);
+
+    let line_height = text_size("", HUD_SCALE).1 as i32 + HUD_SCALE as i32 * 3;
+    let width = lines
+        .iter()
+        .map(|line| text_size(line, HUD_SCALE).0)
+        .max()
+        .unwrap_or(0) as i32;
+    let height = line_height * lines.len() as i32 - HUD_SCALE as i32 * 3;
+
+    let blend = canvas.blend_mode();
+    canvas.set_blend_mode(BlendMode::Blend);
+    canvas.set_draw_color(HUD_PANEL);
+    canvas
+        .fill_rect(Rect::new(
+            HUD_MARGIN,
+            HUD_MARGIN,
+            (width + 2 * HUD_PADDING) as u32,
+            (height + 2 * HUD_PADDING) as u32,
+        ))
+        .unwrap();
+    canvas.set_blend_mode(blend);
+
+    for (i, line) in lines.iter().enumerate() {
+        draw_text(
+            canvas,
+            HUD_MARGIN + HUD_PADDING,
+            HUD_MARGIN + HUD_PADDING + i as i32 * line_height,
+            HUD_SCALE,
+            HUD_TEXT,
+            line,
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use crate::params::SimParam;
+use crate::species::Species;
+use fltk::button::Button;
+use fltk::dialog;
+use fltk::enums::FrameType;
+use fltk::group::Group;
+use fltk::input::Input;
+use fltk::{app, prelude::*, window::Window};
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone)]
+struct SpeciesForm {
+    mass: Input,
+    pop: Input,
+    spread: Input,
+    vis_angle: Input,
+    vis_dist: Input,
+    peak_speed: Input,
+}
+
+#[derive(Clone)]
+struct ParamForm {
+    plant_mass: Input,
+    plant_pop: Input,
+    plant_spread: Input,
+    species: Vec<SpeciesForm>,
+    sim_seed: Input,
+    sim_log_interval: Input,
+    sim_log_path: Input,
+    sim_snapshot_path: Input,
+}
+
+impl SpeciesForm {
+    fn apply(&self, s: &mut Species) {
+        s.mass = self.mass.value().parse().unwrap_or(s.mass);
+        s.pop = self.pop.value().parse().unwrap_or(s.pop);
+        s.spread = self.spread.value().parse().unwrap_or(s.spread);
+        s.vis_a = self.vis_angle.value().parse().unwrap_or(s.vis_a);
+        s.vis_d = self.vis_dist.value().parse().unwrap_or(s.vis_d);
+        s.p_speed = self.peak_speed.value().parse().unwrap_or(s.p_speed);
+    }
+}
+
+impl ParamForm {
+    fn apply(&self, p: &mut SimParam) -> Result<(), String> {
+        let seed = self.sim_seed.value();
+        p.seed = seed
+            .trim()
+            .parse()
+            .map_err(|_| format!("Semente inválida: {seed}"))?;
+        p.pl_mass = self.plant_mass.value().parse().unwrap_or(p.pl_mass);
+        p.pl_pop = self.plant_pop.value().parse().unwrap_or(p.pl_pop);
+        p.pl_spread = self.plant_spread.value().parse().unwrap_or(p.pl_spread);
+        for (form, species) in self.species.iter().zip(&mut p.species) {
+            form.apply(species);
+        }
+        p.log_interval = self
+            .sim_log_interval
+            .value()
+            .parse()
+            .unwrap_or(p.log_interval);
+        p.log_path = self.sim_log_path.value();
+        p.snapshot_path = self.sim_snapshot_path.value();
+
+        Ok(())
+    }
+}
+
+fn new_input_field<T: Into<Option<&'static str>>, D: Display>(
+    x: i32,
+    y: i32,
+    characters: u8,
+    title: T,
+    default: D,
+    group: &mut Group,
+) -> Input {
+    let mut field = Input::new(x, y, characters as i32 * 10, 30, title);
+    field.set_value(&format!("{default}"));
+    group.add(&field);
+
+    field
+}
+
+fn new_species_group(y: i32, species: &Species, win: &mut Window) -> SpeciesForm {
+    let mut group = Group::new(10, y, 480, 80, None);
+    group.set_label(&format!(
+        "Parâmetros para {}:",
+        species.label().to_lowercase()
+    ));
+    group.set_frame(FrameType::DownBox);
+    let mass = new_input_field(110, y + 5, 4, "Massa (g):", species.mass, &mut group);
+    let pop = new_input_field(240, y + 5, 6, "População:", species.pop, &mut group);
+    let spread = new_input_field(
+        420,
+        y + 5,
+        4,
+        "Raio inicial (px):",
+        species.spread,
+        &mut group,
+    );
+    let vis_angle = new_input_field(110, y + 45, 4, "Visão (°):", species.vis_a, &mut group);
+    let vis_dist = new_input_field(240, y + 45, 6, "Visão (px):", species.vis_d, &mut group);
+    let peak_speed = new_input_field(
+        420,
+        y + 45,
+        4,
+        "Velocidade máx:",
+        species.p_speed,
+        &mut group,
+    );
+    group.end();
+    win.add(&group);
+
+    SpeciesForm {
+        mass,
+        pop,
+        spread,
+        vis_angle,
+        vis_dist,
+        peak_speed,
+    }
+}
+
+pub fn param_set(mutex: Arc<Mutex<SimParam>>, config_path: &str) {
+    app::App::default();
+    let p = mutex.lock().unwrap();
+    let sim_y = 85 + 105 * p.species.len() as i32;
+    let mut win = Window::new(100, 100, 500, sim_y + 210, "Aquarium PPP-Sim");
+
+    let mut plant_group = Group::new(10, 20, 480, 40, "Parâmetros para as plantas:");
+    plant_group.set_frame(FrameType::DownBox);
+    let plant_mass = new_input_field(110, 25, 4, "Massa (g):", p.pl_mass, &mut plant_group);
+    let plant_pop = new_input_field(240, 25, 6, "População:", p.pl_pop, &mut plant_group);
+    let plant_spread = new_input_field(
+        420,
+        25,
+        4,
+        "Raio inicial (px):",
+        p.pl_spread,
+        &mut plant_group,
+    );
+    plant_group.end();
+    win.add(&plant_group);
+
+    let species = p
+        .species
+        .iter()
+        .enumerate()
+        .map(|(i, s)| new_species_group(85 + 105 * i as i32, s, &mut win))
+        .collect();
+
+    let mut sim_group = Group::new(10, sim_y, 480, 160, "Parâmetros da simulação:");
+    sim_group.set_frame(FrameType::DownBox);
+    let sim_seed = new_input_field(110, sim_y + 5, 11, "Semente:", p.seed, &mut sim_group);
+    let sim_log_interval = new_input_field(
+        420,
+        sim_y + 5,
+        6,
+        "Registro (ticks):",
+        p.log_interval,
+        &mut sim_group,
+    );
+    let sim_log_path = new_input_field(110, sim_y + 45, 37, "CSV:", &p.log_path, &mut sim_group);
+    let sim_snapshot_path = new_input_field(
+        110,
+        sim_y + 85,
+        37,
+        "Snapshot:",
+        &p.snapshot_path,
+        &mut sim_group,
+    );
+    let sim_config_path =
+        new_input_field(110, sim_y + 125, 27, "Config:", config_path, &mut sim_group);
+    let mut config_button = Button::new(390, sim_y + 125, 90, 30, "Salvar");
+    sim_group.add(&config_button);
+    sim_group.end();
+    win.add(&sim_group);
+
+    drop(p);
+
+    let mut save_button = Button::new(200, sim_y + 170, 100, 30, "Simular!");
+    win.add(&save_button);
+
+    let form = ParamForm {
+        plant_mass,
+        plant_pop,
+        plant_spread,
+        species,
+        sim_seed,
+        sim_log_interval,
+        sim_log_path,
+        sim_snapshot_path,
+    };
+
+    let config_mutex = mutex.clone();
+    let config_form = form.clone();
+    config_button.set_callback(move |_| {
+        let mut guard = config_mutex.lock().unwrap();
+        if let Err(e) = config_form.apply(&mut guard) {
+            dialog::alert_default(&e);
+            return;
+        }
+
+        let path = sim_config_path.value();
+        match guard.save(&path) {
+            Ok(()) => println!("Configuração salva em {path}"),
+            Err(e) => eprintln!("Falha ao salvar configuração: {e}"),
+        }
+    });
+
+    save_button.set_callback(move |_| {
+        let mut guard = mutex.lock().unwrap();
+        if let Err(e) = form.apply(&mut guard) {
+            dialog::alert_default(&e);
+            return;
+        }
+        guard.ready = true;
+
+        println!("Simulação iniciando...");
+        app::quit();
+    });
+
+    win.end();
+    win.show();
+
+    app::run().unwrap();
+}
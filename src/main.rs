@@ -1,25 +1,51 @@
-use crate::bodies::{Position, Vision};
-use crate::fishes::{Fish, Plant};
+use crate::camera::{Camera, ZOOM_STEP};
+use crate::cli::{CliArgs, USAGE};
+use crate::clock::{RateCounter, SimClock};
+use crate::frames::FrameWriter;
+use crate::graph::PopulationGraph;
+use crate::hud::{draw_hud, HudInfo};
+use crate::launcher::param_set;
+use crate::params::SimParam;
+use crate::recorder::Recorders;
+use crate::species::SpeciesRegistry;
 use crate::vectors::Vector2;
-use fltk::button::Button;
-use fltk::enums::FrameType;
-
-use fltk::group::Group;
-use fltk::input::Input;
-use fltk::{app, prelude::*, window::Window};
+use crate::world::{World, TICK_RATE};
 use sdl2::event::Event;
-use sdl2::image::LoadTexture;
 use sdl2::keyboard::Keycode;
+use sdl2::mouse::MouseButton;
 use sdl2::pixels::Color;
 use sdl2::render::{Texture, TextureCreator, WindowCanvas};
 use sdl2::video::WindowContext;
 use sdl2::EventPump;
-use std::sync::{Arc, Mutex, MutexGuard};
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 mod bodies;
+mod bounds;
+mod brain;
+mod camera;
+mod cli;
+mod clock;
 mod fishes;
+mod frames;
+mod genome;
+mod graph;
+mod hud;
+mod launcher;
+mod obstacles;
+mod params;
+mod recorder;
+mod scene;
+mod snapshot;
+mod spatial;
+mod species;
+mod steering;
+mod sweep;
+mod text;
 mod vectors;
+mod world;
+
+const FRAME_SIZE: (u32, u32) = (1280, 720);
 
 struct ScreenControl {
     up: bool,
@@ -39,27 +65,69 @@ impl ScreenControl {
     }
 }
 
+#[derive(Clone, Copy, PartialEq)]
+enum MouseTool {
+    Pan,
+    Plant,
+    Fish(usize),
+    Delete,
+}
+
+impl MouseTool {
+    fn from_key(key: Keycode, species: usize) -> Option<Self> {
+        let tool = match key {
+            Keycode::Num1 => MouseTool::Pan,
+            Keycode::Num2 => MouseTool::Plant,
+            Keycode::Num3 => MouseTool::Fish(0),
+            Keycode::Num4 => MouseTool::Fish(1),
+            Keycode::Num5 => MouseTool::Fish(2),
+            Keycode::Num6 => MouseTool::Fish(3),
+            Keycode::Num7 => MouseTool::Fish(4),
+            Keycode::Num8 => MouseTool::Fish(5),
+            Keycode::Num9 => MouseTool::Fish(6),
+            Keycode::Delete | Keycode::Backspace => MouseTool::Delete,
+            _ => return None,
+        };
+
+        match tool {
+            MouseTool::Fish(index) if index >= species => None,
+            tool => Some(tool),
+        }
+    }
+
+    fn name(&self, species: &SpeciesRegistry) -> String {
+        match self {
+            MouseTool::Pan => String::from("mover"),
+            MouseTool::Plant => String::from("planta"),
+            MouseTool::Fish(index) => species.get(*index).label().to_string(),
+            MouseTool::Delete => String::from("remover"),
+        }
+    }
+}
+
 struct Aquarium<'a> {
-    offset_window: Vector2,
-    offset_zoom: f64,
+    camera: Camera,
+    debug: bool,
+    hud: bool,
+    graph: PopulationGraph,
+    show_graph: bool,
 
     textures: Vec<Texture<'a>>,
 
-    plants: Vec<Plant>,
-    preys: Vec<Fish>,
-    predators: Vec<Fish>,
+    world: World,
 }
 
 impl<'a> Aquarium<'a> {
-    fn create() -> Result<Self, String> {
+    fn create(world: World, graph_seconds: u32) -> Result<Self, String> {
         Ok(Self {
-            offset_window: Vector2::default(),
-            offset_zoom: 0.0,
+            camera: Camera::new(Vector2::default()),
+            debug: false,
+            hud: true,
+            graph: PopulationGraph::new(graph_seconds, world.species()),
+            show_graph: false,
             textures: vec![],
 
-            plants: vec![],
-            preys: vec![],
-            predators: vec![],
+            world,
         })
     }
 
@@ -67,311 +135,202 @@ impl<'a> Aquarium<'a> {
         &mut self,
         canvas: &mut WindowCanvas,
         tex_creator: &'a TextureCreator<WindowContext>,
-        parameters: MutexGuard<SimParam>,
     ) -> &mut Self {
-        self.textures
-            .push(tex_creator.load_texture("assets/seaweed.png").unwrap());
-        self.textures
-            .push(tex_creator.load_texture("assets/fish.png").unwrap());
-        self.textures
-            .push(tex_creator.load_texture("assets/shark.png").unwrap());
+        self.textures = scene::load_textures(tex_creator, &self.world).unwrap();
 
         canvas.set_draw_color(Color::RGB(0, 0, 0));
         canvas.clear();
         canvas.present();
 
-        let screen_center = Vector2::new(1820.0 / 2.0, 1080.0 / 2.0);
-        for _i in 0..parameters.pl_pop as i32 {
-            let new_pos = screen_center + Vector2::random_in_radius(parameters.pl_spread);
-            self.plants
-                .push(Plant::new(new_pos, parameters.pl_mass / 10.0));
-        }
-
-        for _i in 0..parameters.pr_pop as i32 {
-            let new_pos = screen_center + Vector2::random_in_radius(parameters.pr_spread);
-            self.preys.push(Fish::new(
-                new_pos,
-                parameters.pr_mass / 10.0,
-                parameters.pr_vis_a,
-                parameters.pr_vis_d,
-                parameters.pr_p_speed,
-            ));
-        }
-
-        for _i in 0..parameters.pd_pop as i32 {
-            let new_pos = screen_center + Vector2::random_in_radius(parameters.pd_spread);
-            self.predators.push(Fish::new(
-                new_pos,
-                parameters.pd_mass / 10.0,
-                parameters.pd_vis_a,
-                parameters.pd_vis_d,
-                parameters.pd_p_speed,
-            ));
-        }
-
         self
     }
 
-    fn check_proximity<O: Vision, T: Position>(
-        origin: &O,
-        vec: &Vec<T>,
-    ) -> Option<(Vector2, Vector2)> {
-        let mut closest_tgt = None;
-        let mut min_dist = f64::MAX;
-        for tgt in vec {
-            let tgt_dist_sqr = origin.in_sight(tgt.pos());
-
-            if (tgt_dist_sqr > 0.0) && (tgt_dist_sqr < min_dist) {
-                min_dist = tgt_dist_sqr;
-                closest_tgt = Some((tgt.pos(), tgt.vel()));
-            }
-        }
-
-        closest_tgt
-    }
-
-    fn process_plants(&mut self, canvas: &mut WindowCanvas, do_grow: bool) {
-        let mut i = self.plants.len();
-        while i != 0 {
-            i -= 1;
-            let closest_prey = Aquarium::check_proximity(&self.plants[i], &self.preys);
-
-            match closest_prey {
-                None => {
-                    if do_grow {
-                        let rootlings = self.plants[i].grow();
-                        match rootlings {
-                            None => {}
-                            Some((rootling_1, rootling_2)) => {
-                                self.plants.push(rootling_1);
-                                self.plants.push(rootling_2);
-                            }
-                        }
-                    }
-                }
-                Some((_prey_pos, _)) => {
-                    self.plants[i].health -= 1;
-                    if self.plants[i].health < 3 {
-                        self.plants.swap_remove(i);
-                        continue;
-                    }
-                }
-            }
-            self.plants[i].draw(canvas, &self.textures[0], self.offset_window);
-        }
+    fn render(&mut self, canvas: &mut WindowCanvas) {
+        scene::render(
+            canvas,
+            &mut self.world,
+            &self.textures,
+            &self.camera,
+            self.debug,
+        );
     }
 
-    fn process_preys(&mut self, canvas: &mut WindowCanvas) {
-        let mut i = self.preys.len();
-        while i != 0 {
-            i -= 1;
-            let closest_plant = Aquarium::check_proximity(&self.preys[i], &self.plants);
-            let closest_predator = Aquarium::check_proximity(&self.preys[i], &self.predators);
-
-            match closest_predator {
-                Some((preadator_pos, _predator_vel)) => {
-                    self.preys[i].flee(preadator_pos);
-                    if (preadator_pos - self.preys[i].pos()).length() < 10.0 {
-                        self.preys.swap_remove(i);
-                        continue;
-                    }
-                }
-                None => match closest_plant {
-                    Some((plant_pos, _)) => {
-                        self.preys[i].arrive(plant_pos);
-                    }
-                    None => {
-                        self.preys[i].wander();
-                    }
-                },
-            }
-
-            self.preys[i].draw(canvas, &self.textures[1], self.offset_window);
-        }
-    }
-
-    fn process_predators(&mut self, canvas: &mut WindowCanvas) {
-        let mut i = self.predators.len();
-        while i != 0 {
-            i -= 1;
-            let closest_prey = Aquarium::check_proximity(&self.predators[i], &self.preys);
-
-            match closest_prey {
-                Some((prey_pos, prey_vel)) => {
-                    self.predators[i].pursuit(prey_pos, prey_vel);
-                }
-                None => {
-                    self.predators[i].wander();
-                }
-            }
-
-            self.predators[i].draw(canvas, &self.textures[2], self.offset_window);
-        }
-    }
-
-    fn process_screen_sliding(&mut self, arrows: &ScreenControl) {
-        let speed = 6.0;
+    fn process_screen_sliding(&mut self, arrows: &ScreenControl, frame_time: f64) {
+        let speed = 360.0 * frame_time;
         if arrows.up {
-            self.offset_window.offset(0.0, speed);
+            self.camera.pan(0.0, speed);
         }
         if arrows.down {
-            self.offset_window.offset(0.0, -speed);
+            self.camera.pan(0.0, -speed);
         }
         if arrows.right {
-            self.offset_window.offset(-speed, 0.0);
+            self.camera.pan(-speed, 0.0);
         }
         if arrows.left {
-            self.offset_window.offset(speed, 0.0);
+            self.camera.pan(speed, 0.0);
         }
     }
-}
-
-struct SimParam {
-    pl_mass: f64,
-    pr_mass: f64,
-    pd_mass: f64,
-    pl_pop: f64,
-    pr_pop: f64,
-    pd_pop: f64,
-    pl_spread: f64,
-    pr_spread: f64,
-    pd_spread: f64,
-    pr_vis_a: f64,
-    pd_vis_a: f64,
-    pr_vis_d: f64,
-    pd_vis_d: f64,
-    pr_p_speed: f64,
-    pd_p_speed: f64,
-    ready: bool,
-}
 
-impl SimParam {
-    fn default() -> Self {
-        SimParam {
-            pl_mass: 0.0,
-            pr_mass: 0.0,
-            pd_mass: 0.0,
-            pl_pop: 0.0,
-            pr_pop: 0.0,
-            pd_pop: 0.0,
-            pl_spread: 0.0,
-            pr_spread: 0.0,
-            pd_spread: 0.0,
-            pr_vis_a: 0.0,
-            pd_vis_a: 0.0,
-            pr_vis_d: 0.0,
-            pd_vis_d: 0.0,
-            pr_p_speed: 0.0,
-            pd_p_speed: 0.0,
-            ready: false,
+    fn apply_tool(&mut self, tool: MouseTool, mouse: Vector2) {
+        let pos = self.camera.screen_to_world(mouse);
+        match tool {
+            MouseTool::Pan => {}
+            MouseTool::Plant => self.world.spawn_plant(pos),
+            MouseTool::Fish(index) => self.world.spawn_fish(index, pos),
+            MouseTool::Delete => {
+                let (x, y) = mouse.get_components();
+                self.world.remove_at(x as i32, y as i32);
+            }
         }
     }
-}
 
-fn new_input_field<T: Into<Option<&'static str>>>(
-    x: i32,
-    y: i32,
-    characters: u8,
-    title: T,
-    default: i32,
-    group: &mut Group,
-) -> Input {
-    let mut field = Input::new(x, y, (characters * 10) as i32, 30, title);
-    field.set_value(&*format!("{default}"));
-    group.add(&field);
-
-    field
+    fn zoom_at_center(&mut self, canvas: &WindowCanvas, factor: f64) -> Result<(), String> {
+        let (w, h) = canvas.output_size()?;
+        self.camera
+            .zoom_at(Vector2::new(w as f64 / 2.0, h as f64 / 2.0), factor);
+        Ok(())
+    }
 }
-fn param_set(mutex: Arc<Mutex<SimParam>>) {
-    app::App::default();
-    let mut win = Window::new(100, 100, 500, 320, "Aquarium PPP-Sim");
-
-    let mut plant_group = Group::new(10, 20, 480, 40, "Parâmetros para as plantas:");
-    plant_group.set_frame(FrameType::DownBox);
-    let plant_mass = new_input_field(110, 25, 4, "Massa (g):", 250, &mut plant_group);
-    let plant_pop = new_input_field(240, 25, 6, "População:", 10, &mut plant_group);
-    let plant_spread = new_input_field(420, 25, 4, "Raio inicial (px):", 500, &mut plant_group);
-
-    let mut prey_group = Group::new(10, 85, 480, 80, "Parâmetros para as presas:");
-    prey_group.set_frame(FrameType::DownBox);
-    let prey_mass = new_input_field(110, 90, 4, "Massa (g):", 200, &mut prey_group);
-    let prey_pop = new_input_field(240, 90, 6, "População:", 5, &mut prey_group);
-    let prey_spread = new_input_field(420, 90, 4, "Raio inicial (px):", 300, &mut prey_group);
-    let prey_vis_angle = new_input_field(110, 130, 4, "Visão (°):", 179, &mut prey_group);
-    let prey_vis_dist = new_input_field(240, 130, 6, "Visão (px):", 800, &mut prey_group);
-    let prey_peak_speed = new_input_field(420, 130, 4, "Velocidade máx:", 5, &mut prey_group);
-
-    let mut pred_group = Group::new(10, 190, 480, 80, "Parâmetros para os predadores:");
-    pred_group.set_frame(FrameType::DownBox);
-    let pred_mass = new_input_field(110, 195, 4, "Massa (g):", 800, &mut pred_group);
-    let pred_pop = new_input_field(240, 195, 6, "População:", 2, &mut pred_group);
-    let pred_spread = new_input_field(420, 195, 4, "Raio inicial (px):", 500, &mut pred_group);
-    let pred_vis_angle = new_input_field(110, 235, 4, "Visão (°):", 120, &mut pred_group);
-    let pred_vis_dist = new_input_field(240, 235, 6, "Visão (px):", 1000, &mut pred_group);
-    let pred_peak_speed = new_input_field(420, 235, 4, "Velocidade máx:", 6, &mut pred_group);
-
-    win.add(&plant_group);
-    win.add(&prey_group);
-    win.add(&pred_group);
-
-    let mut save_button = Button::new(200, 280, 100, 30, "Simular!");
-    win.add(&save_button);
-
-    save_button.set_callback(move |_| {
-        let mut guard = mutex.lock().unwrap();
-
-        *guard = SimParam {
-            pl_mass: plant_mass.value().parse().unwrap_or(1.0),
-            pr_mass: prey_mass.value().parse().unwrap_or(1.0),
-            pd_mass: pred_mass.value().parse().unwrap_or(1.0),
-            pl_pop: plant_pop.value().parse().unwrap_or(0.0),
-            pr_pop: prey_pop.value().parse().unwrap_or(0.0),
-            pd_pop: pred_pop.value().parse().unwrap_or(0.0),
-            pl_spread: plant_spread.value().parse().unwrap_or(10.0),
-            pr_spread: prey_spread.value().parse().unwrap_or(10.0),
-            pd_spread: pred_spread.value().parse().unwrap_or(10.0),
-            pr_vis_a: prey_vis_angle.value().parse().unwrap_or(0.0),
-            pd_vis_a: pred_vis_angle.value().parse().unwrap_or(0.0),
-            pr_vis_d: prey_vis_dist.value().parse().unwrap_or(0.0),
-            pd_vis_d: pred_vis_dist.value().parse().unwrap_or(0.0),
-            pr_p_speed: prey_peak_speed.value().parse().unwrap_or(0.0),
-            pd_p_speed: pred_peak_speed.value().parse().unwrap_or(0.0),
-            ready: true,
+
+pub fn main() -> Result<(), String> {
+    let args = CliArgs::parse(std::env::args().skip(1))?;
+    if args.help {
+        println!("{USAGE}");
+        return Ok(());
+    }
+    if args.headless && args.tick_limit.is_none() && args.sweep_path.is_none() {
+        eprintln!("{USAGE}");
+        return Err(String::from("--headless requer --ticks <n>"));
+    }
+
+    let mut parameters = args.load_parameters()?;
+    if let Some(sweep_path) = &args.sweep_path {
+        return sweep::run(sweep_path, &parameters);
+    }
+    if args.headless || args.skip_dialog {
+        parameters.ready = true;
+    } else {
+        let mutex: Arc<Mutex<SimParam>> = Arc::new(Mutex::new(parameters));
+        param_set(mutex.clone(), &args.config_path);
+        parameters = std::mem::take(&mut *mutex.lock().unwrap());
+    }
+    if !parameters.ready {
+        return Err(String::from("Simulação cancelada."));
+    }
+
+    let (world, camera) = if parameters.snapshot_path.is_empty() {
+        (World::new(&parameters)?, Camera::new(Vector2::default()))
+    } else {
+        let (world, camera) = snapshot::load(&parameters.snapshot_path)?;
+        parameters = SimParam {
+            log_path: parameters.log_path,
+            log_interval: parameters.log_interval,
+            trait_log_path: parameters.trait_log_path,
+            snapshot_path: parameters.snapshot_path,
+            graph_seconds: parameters.graph_seconds,
+            frames_path: parameters.frames_path,
+            frames_interval: parameters.frames_interval,
+            ..world.params().clone()
         };
+        (world, camera)
+    };
+    println!("Semente: {}", world.seed());
+
+    let mut recorders = Recorders::create(&parameters, &world)?;
+
+    if args.headless {
+        run_headless(world, &parameters, &mut recorders, &args)?;
+    } else {
+        run_windowed(
+            world,
+            camera,
+            parameters.graph_seconds,
+            &mut recorders,
+            &args,
+        )?;
+    }
 
-        println!("Simulação iniciando...");
-        app::quit();
-    });
+    recorders.flush()?;
 
-    win.end();
-    win.show();
+    Ok(())
+}
+
+fn run_headless(
+    world: World,
+    parameters: &SimParam,
+    recorders: &mut Recorders,
+    args: &CliArgs,
+) -> Result<(), String> {
+    if parameters.frames_path.is_empty() {
+        return simulate(world, recorders, args.tick_limit, |_| Ok(()));
+    }
 
-    app::run().unwrap();
+    let size = args.window_size.unwrap_or(FRAME_SIZE);
+    let mut canvas = frames::create_canvas(size.0, size.1)?;
+    let tex_creator = canvas.texture_creator();
+    let camera = Camera::fit(size, (parameters.world_width, parameters.world_height));
+    let mut frames = FrameWriter::create(
+        &tex_creator,
+        &parameters.frames_path,
+        parameters.frames_interval,
+        camera,
+        &world,
+    )?;
+
+    simulate(world, recorders, args.tick_limit, |world| {
+        frames.record(&mut canvas, world)
+    })
 }
 
-pub fn main() -> Result<(), String> {
-    let mutex: Arc<Mutex<SimParam>> = Arc::new(Mutex::new(SimParam::default()));
-    param_set(mutex.clone());
-    let guard = mutex.lock().unwrap();
-    if !guard.ready {
-        return Err(String::from("Simulação cancelada."));
+fn simulate<F: FnMut(&mut World) -> Result<(), String>>(
+    mut world: World,
+    recorders: &mut Recorders,
+    tick_limit: Option<u64>,
+    mut on_tick: F,
+) -> Result<(), String> {
+    let tick_end = tick_limit.map(|limit| world.ticks() + limit);
+    while tick_end.is_none_or(|end| world.ticks() < end) {
+        world.step();
+        recorders.record(&world)?;
+        on_tick(&mut world)?;
     }
 
-    let mut pl_p = guard.pl_pop;
-    let mut pr_p = guard.pr_pop;
-    let mut pd_p = guard.pd_pop;
+    let mut summary = format!("Tick {}: {} plantas", world.ticks(), world.plants.len());
+    for (species, fishes) in world.species().iter().zip(&world.fishes) {
+        summary += &format!(", {} {}", fishes.len(), species.label().to_lowercase());
+    }
+    println!("{summary}");
 
-    let (mut canvas, mut event_pump) = sdl_init()?;
+    Ok(())
+}
+
+fn run_windowed(
+    world: World,
+    camera: Camera,
+    graph_seconds: u32,
+    recorders: &mut Recorders,
+    args: &CliArgs,
+) -> Result<(), String> {
+    let (mut canvas, mut event_pump) = sdl_init(args.window_size)?;
     let tex_creator = &canvas.texture_creator();
-    let mut aquarium = Aquarium::create()?;
+    let mut aquarium = Aquarium::create(world, graph_seconds)?;
+    aquarium.camera = camera;
 
-    aquarium.init(&mut canvas, tex_creator, guard);
+    aquarium.init(&mut canvas, tex_creator);
 
     let mut arrows = ScreenControl::new();
+    let mut mouse = Vector2::default();
+    let mut tool = MouseTool::Pan;
+    let mut dragging = false;
+    let mut step_once = false;
     let fps = 60;
-    let mut ticks = 0;
-    'running: loop {
-        ticks += 1;
+    let mut clock = SimClock::new(TICK_RATE);
+    let mut frame_rate = RateCounter::new();
+    let mut tick_rate = RateCounter::new();
+    let mut last_frame = Instant::now();
+    let tick_end = args.tick_limit.map(|limit| aquarium.world.ticks() + limit);
+    'running: while tick_end.is_none_or(|end| aquarium.world.ticks() < end) {
+        let frame_start = Instant::now();
         for event in event_pump.poll_iter() {
             match event {
                 Event::Quit { .. }
@@ -379,6 +338,100 @@ pub fn main() -> Result<(), String> {
                     keycode: Some(Keycode::Escape),
                     ..
                 } => break 'running,
+                Event::KeyDown {
+                    keycode: Some(Keycode::F5),
+                    ..
+                } => {
+                    let path = format!("snapshot_{}.json", aquarium.world.ticks());
+                    match snapshot::save(&path, &aquarium.world, aquarium.camera) {
+                        Ok(()) => println!("Snapshot salvo em {path}"),
+                        Err(e) => eprintln!("Falha ao salvar snapshot: {e}"),
+                    }
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::F3),
+                    ..
+                } => aquarium.debug = !aquarium.debug,
+                Event::KeyDown {
+                    keycode: Some(Keycode::Space),
+                    ..
+                } => clock.toggle_pause(),
+                Event::KeyDown {
+                    keycode: Some(Keycode::S),
+                    ..
+                } if clock.is_paused() => step_once = true,
+                Event::KeyDown {
+                    keycode: Some(Keycode::RightBracket),
+                    ..
+                } => clock.faster(),
+                Event::KeyDown {
+                    keycode: Some(Keycode::LeftBracket),
+                    ..
+                } => clock.slower(),
+                Event::KeyDown {
+                    keycode: Some(Keycode::H),
+                    ..
+                } => aquarium.hud = !aquarium.hud,
+                Event::KeyDown {
+                    keycode: Some(Keycode::G),
+                    ..
+                } => aquarium.show_graph = !aquarium.show_graph,
+                Event::KeyDown {
+                    keycode: Some(Keycode::B),
+                    ..
+                } => {
+                    let ticks = aquarium.world.ticks();
+                    let saved = aquarium
+                        .world
+                        .save_brains(|species| format!("brains_{}_{ticks}.json", species.name));
+                    match saved {
+                        Ok(paths) if paths.is_empty() => println!("Nenhum peixe com cérebro"),
+                        Ok(paths) => println!("Cérebros salvos em {}", paths.join(", ")),
+                        Err(e) => eprintln!("Falha ao salvar cérebros: {e}"),
+                    }
+                }
+                Event::MouseMotion {
+                    x, y, xrel, yrel, ..
+                } => {
+                    mouse = Vector2::new(x as f64, y as f64);
+                    if dragging {
+                        aquarium.camera.pan(xrel as f64, yrel as f64);
+                    }
+                }
+                Event::MouseButtonDown {
+                    mouse_btn, x, y, ..
+                } => {
+                    mouse = Vector2::new(x as f64, y as f64);
+                    match mouse_btn {
+                        MouseButton::Left if tool != MouseTool::Pan => {
+                            aquarium.apply_tool(tool, mouse)
+                        }
+                        MouseButton::Left | MouseButton::Right => dragging = true,
+                        _ => {}
+                    }
+                }
+                Event::MouseButtonUp {
+                    mouse_btn: MouseButton::Left | MouseButton::Right,
+                    ..
+                } => dragging = false,
+                Event::KeyDown {
+                    keycode: Some(key), ..
+                } if MouseTool::from_key(key, aquarium.world.species().count()).is_some() => {
+                    tool = MouseTool::from_key(key, aquarium.world.species().count()).unwrap();
+                    println!("Ferramenta: {}", tool.name(aquarium.world.species()));
+                }
+                Event::MouseWheel { y, .. } if y != 0 => {
+                    let factor = if y > 0 { ZOOM_STEP } else { 1.0 / ZOOM_STEP };
+                    aquarium.camera.zoom_at(mouse, factor);
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::Plus | Keycode::Equals | Keycode::KpPlus),
+                    ..
+                } => aquarium.zoom_at_center(&canvas, ZOOM_STEP)?,
+                Event::KeyDown {
+                    keycode: Some(Keycode::Minus | Keycode::KpMinus),
+                    ..
+                } => aquarium.zoom_at_center(&canvas, 1.0 / ZOOM_STEP)?,
                 Event::KeyDown {
                     keycode: Some(Keycode::Up),
                     ..
@@ -433,37 +486,58 @@ pub fn main() -> Result<(), String> {
             }
         }
 
-        fill_bg(&mut canvas, Color::BLACK);
-
-        let time_act = ticks > 1 * fps;
-        aquarium.process_plants(&mut canvas, time_act);
-        aquarium.process_preys(&mut canvas);
-        aquarium.process_predators(&mut canvas);
-        aquarium.process_screen_sliding(&arrows);
+        let mut ticks_done = 0;
+        let pending = clock.pending_ticks() + std::mem::take(&mut step_once) as u32;
+        for _ in 0..pending {
+            if tick_end.is_some_and(|end| aquarium.world.ticks() >= end) {
+                break;
+            }
 
-        if time_act {
-            ticks = 0;
+            aquarium.world.step();
+            recorders.record(&aquarium.world)?;
+            aquarium.graph.record(&aquarium.world);
+            ticks_done += 1;
         }
+        tick_rate.count(ticks_done);
 
-        update_screen(&mut canvas, Some(fps));
-
-        /*
-        if pl_p as usize != aquarium.plants.len()
-            || pr_p as usize != aquarium.preys.len()
-            || pd_p as usize != aquarium.predators.len()
-        {
-            pl_p = aquarium.plants.len() as f64;
-            pr_p = aquarium.preys.len() as f64;
-            pd_p = aquarium.predators.len() as f64;
-
-            println!(
-                "{}, {}, {}",
-                aquarium.plants.len(),
-                aquarium.predators.len(),
-                aquarium.preys.len()
+        aquarium.process_screen_sliding(&arrows, (frame_start - last_frame).as_secs_f64());
+        last_frame = frame_start;
+
+        fill_bg(&mut canvas, Color::BLACK);
+        aquarium.render(&mut canvas);
+        if aquarium.show_graph {
+            aquarium.graph.draw(&mut canvas)?;
+        }
+        if aquarium.hud {
+            draw_hud(
+                &mut canvas,
+                &HudInfo {
+                    plants: aquarium.world.plants.len(),
+                    fishes: aquarium
+                        .world
+                        .species()
+                        .iter()
+                        .zip(&aquarium.world.fishes)
+                        .map(|(species, fishes)| (species.label().to_string(), fishes.len()))
+                        .collect(),
+                    sim_seconds: aquarium.world.ticks() as f64 / TICK_RATE as f64,
+                    tick_rate: tick_rate.rate(),
+                    fps: frame_rate.rate(),
+                    seed: aquarium.world.seed(),
+                    paused: clock.is_paused(),
+                    speed: clock.speed(),
+                },
             );
-            }
-            */
+        }
+
+        update_screen(&mut canvas, frame_start, Some(fps));
+        if frame_rate.count(1) {
+            let title = format!("Behaviours - {:.1} FPS", frame_rate.rate());
+            canvas
+                .window_mut()
+                .set_title(&title)
+                .map_err(|e| e.to_string())?;
+        }
     }
 
     Ok(())
@@ -474,30 +548,28 @@ fn fill_bg(w_canvas: &mut WindowCanvas, color: Color) {
     w_canvas.clear();
 }
 
-fn update_screen(w_canvas: &mut WindowCanvas, fps: Option<u64>) {
+fn update_screen(w_canvas: &mut WindowCanvas, frame_start: Instant, fps: Option<u32>) {
     w_canvas.present();
-    match fps {
-        None => {
-            return;
-        }
-        Some(_) => {}
-    }
-    std::thread::sleep(Duration::from_millis(1000 / fps.unwrap()));
+    let Some(fps) = fps else {
+        return;
+    };
+    let frame_time = Duration::from_secs(1) / fps;
+    std::thread::sleep(frame_time.saturating_sub(frame_start.elapsed()));
 }
 
-fn sdl_init() -> Result<(WindowCanvas, EventPump), String> {
+fn sdl_init(window_size: Option<(u32, u32)>) -> Result<(WindowCanvas, EventPump), String> {
     let sdl_context = sdl2::init()?;
 
     let video_subsystem = sdl_context.video()?;
-    let window = video_subsystem
-        .window("Behaviours", 800, 600)
-        .position_centered()
-        .opengl()
-        .fullscreen_desktop()
-        .build()
-        .map_err(|e| e.to_string())?;
+    let (width, height) = window_size.unwrap_or((800, 600));
+    let mut window_builder = video_subsystem.window("Behaviours", width, height);
+    window_builder.position_centered().opengl();
+    if window_size.is_none() {
+        window_builder.fullscreen_desktop();
+    }
+    let window = window_builder.build().map_err(|e| e.to_string())?;
     let event_pump = sdl_context.event_pump()?;
     let canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
 
     Ok((canvas, event_pump))
-}
\ No newline at end of file
+}
@@ -0,0 +1,232 @@
+use crate::camera::Camera;
+use crate::vectors::Vector2;
+use sdl2::pixels::Color;
+use sdl2::rect::Point;
+use sdl2::render::{Canvas, RenderTarget};
+use serde::{Deserialize, Serialize};
+
+const OBSTACLE_COLOR: Color = Color::RGB(70, 62, 56);
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "shape", rename_all = "lowercase")]
+pub enum Obstacle {
+    Circle { center: [f64; 2], radius: f64 },
+    Polygon { points: Vec<[f64; 2]> },
+}
+
+fn point(p: [f64; 2]) -> Vector2 {
+    Vector2::new(p[0], p[1])
+}
+
+fn closest_on_segment(p: Vector2, a: Vector2, b: Vector2) -> Vector2 {
+    let ab = b - a;
+    let len_sqr = ab.length_sqr();
+    if len_sqr == 0.0 {
+        return a;
+    }
+
+    let t = ((p - a).dot(ab) / len_sqr).clamp(0.0, 1.0);
+    a + ab * t
+}
+
+impl Obstacle {
+    fn edges(points: &[[f64; 2]]) -> impl Iterator<Item = (Vector2, Vector2)> + '_ {
+        points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .map(|(&a, &b)| (point(a), point(b)))
+    }
+
+    pub fn contains(&self, p: Vector2) -> bool {
+        match self {
+            Obstacle::Circle { center, radius } => (p - point(*center)).length() < *radius,
+            Obstacle::Polygon { points } => {
+                let (px, py) = p.get_components();
+                let mut inside = false;
+                for (a, b) in Obstacle::edges(points) {
+                    let ((ax, ay), (bx, by)) = (a.get_components(), b.get_components());
+                    if (ay > py) != (by > py) && px < ax + (py - ay) * (bx - ax) / (by - ay) {
+                        inside = !inside;
+                    }
+                }
+                inside
+            }
+        }
+    }
+
+    pub fn escape(&self, p: Vector2) -> Option<Vector2> {
+        if !self.contains(p) {
+            return None;
+        }
+
+        match self {
+            Obstacle::Circle { center, radius } => {
+                let center = point(*center);
+                let out = p - center;
+                if out.length_sqr() == 0.0 {
+                    return Some(center + Vector2::new(*radius, 0.0));
+                }
+                Some(center + out.mag(*radius))
+            }
+            Obstacle::Polygon { points } => Obstacle::edges(points)
+                .map(|(a, b)| closest_on_segment(p, a, b))
+                .min_by(|q1, q2| (*q1 - p).length_sqr().total_cmp(&(*q2 - p).length_sqr())),
+        }
+    }
+
+    pub fn draw<T: RenderTarget>(&self, canvas: &mut Canvas<T>, camera: &Camera) {
+        let color = canvas.draw_color();
+        canvas.set_draw_color(OBSTACLE_COLOR);
+
+        match self {
+            Obstacle::Circle { center, radius } => {
+                let (cx, cy) = camera.world_to_screen(point(*center)).get_components();
+                let radius = radius * camera.zoom;
+                let r = radius.round() as i32;
+                for dy in -r..=r {
+                    let half = ((radius * radius) - (dy * dy) as f64).max(0.0).sqrt() as i32;
+                    let y = cy as i32 + dy;
+                    canvas
+                        .draw_line(
+                            Point::new(cx as i32 - half, y),
+                            Point::new(cx as i32 + half, y),
+                        )
+                        .unwrap();
+                }
+            }
+            Obstacle::Polygon { points } => {
+                let screen: Vec<(f64, f64)> = points
+                    .iter()
+                    .map(|&p| camera.world_to_screen(point(p)).get_components())
+                    .collect();
+                let min_y = screen.iter().map(|p| p.1).fold(f64::MAX, f64::min) as i32;
+                let max_y = screen.iter().map(|p| p.1).fold(f64::MIN, f64::max) as i32;
+
+                for y in min_y..=max_y {
+                    let scan = y as f64 + 0.5;
+                    let mut crossings: Vec<f64> = screen
+                        .iter()
+                        .zip(screen.iter().cycle().skip(1))
+                        .filter(|(a, b)| (a.1 > scan) != (b.1 > scan))
+                        .map(|(a, b)| a.0 + (scan - a.1) * (b.0 - a.0) / (b.1 - a.1))
+                        .collect();
+                    crossings.sort_by(f64::total_cmp);
+
+                    for pair in crossings.chunks_exact(2) {
+                        canvas
+                            .draw_line(Point::new(pair[0] as i32, y), Point::new(pair[1] as i32, y))
+                            .unwrap();
+                    }
+                }
+            }
+        }
+
+        canvas.set_draw_color(color);
+    }
+}
+
+pub fn escape_all(obstacles: &[Obstacle], p: Vector2) -> Option<Vector2> {
+    obstacles.iter().find_map(|obstacle| obstacle.escape(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn circle() -> Obstacle {
+        Obstacle::Circle {
+            center: [50.0, 50.0],
+            radius: 10.0,
+        }
+    }
+
+    fn u_shape() -> Obstacle {
+        Obstacle::Polygon {
+            points: vec![
+                [0.0, 0.0],
+                [30.0, 0.0],
+                [30.0, 30.0],
+                [20.0, 30.0],
+                [20.0, 10.0],
+                [10.0, 10.0],
+                [10.0, 30.0],
+                [0.0, 30.0],
+            ],
+        }
+    }
+
+    fn assert_close(p: Option<Vector2>, x: f64, y: f64) {
+        let (px, py) = p.expect("esperado um ponto de fuga").get_components();
+        assert!(
+            (px - x).abs() < 1e-9 && (py - y).abs() < 1e-9,
+            "({px}, {py})"
+        );
+    }
+
+    #[test]
+    fn circle_contains_only_points_within_its_radius() {
+        assert!(circle().contains(Vector2::new(55.0, 50.0)));
+        assert!(!circle().contains(Vector2::new(61.0, 50.0)));
+        assert!(!circle().contains(Vector2::new(58.0, 58.0)));
+    }
+
+    #[test]
+    fn concave_polygon_excludes_its_notch() {
+        let u = u_shape();
+        assert!(u.contains(Vector2::new(5.0, 20.0)));
+        assert!(u.contains(Vector2::new(25.0, 20.0)));
+        assert!(u.contains(Vector2::new(15.0, 5.0)));
+        assert!(!u.contains(Vector2::new(15.0, 20.0)));
+        assert!(!u.contains(Vector2::new(40.0, 5.0)));
+        assert!(!u.contains(Vector2::new(-1.0, 15.0)));
+    }
+
+    #[test]
+    fn escape_leaves_through_the_nearest_edge() {
+        let u = u_shape();
+        assert_close(u.escape(Vector2::new(15.0, 3.0)), 15.0, 0.0);
+        assert_close(u.escape(Vector2::new(15.0, 8.0)), 15.0, 10.0);
+        assert_close(u.escape(Vector2::new(2.0, 20.0)), 0.0, 20.0);
+        assert!(u.escape(Vector2::new(15.0, 20.0)).is_none());
+
+        assert_close(circle().escape(Vector2::new(50.0, 47.0)), 50.0, 40.0);
+        assert!(circle().escape(Vector2::new(70.0, 50.0)).is_none());
+    }
+
+    #[test]
+    fn escape_from_the_circle_centre_picks_a_point_on_the_rim() {
+        assert_close(circle().escape(Vector2::new(50.0, 50.0)), 60.0, 50.0);
+    }
+
+    #[test]
+    fn closest_on_segment_clamps_to_the_endpoints() {
+        let (a, b) = (Vector2::new(0.0, 0.0), Vector2::new(10.0, 0.0));
+        assert_close(
+            Some(closest_on_segment(Vector2::new(4.0, 3.0), a, b)),
+            4.0,
+            0.0,
+        );
+        assert_close(
+            Some(closest_on_segment(Vector2::new(-5.0, 3.0), a, b)),
+            0.0,
+            0.0,
+        );
+        assert_close(
+            Some(closest_on_segment(Vector2::new(15.0, -2.0), a, b)),
+            10.0,
+            0.0,
+        );
+        assert_close(
+            Some(closest_on_segment(Vector2::new(1.0, 1.0), a, a)),
+            0.0,
+            0.0,
+        );
+    }
+
+    #[test]
+    fn escape_all_uses_the_obstacle_holding_the_point() {
+        let obstacles = [u_shape(), circle()];
+        assert_close(escape_all(&obstacles, Vector2::new(50.0, 53.0)), 50.0, 60.0);
+        assert!(escape_all(&obstacles, Vector2::new(100.0, 100.0)).is_none());
+    }
+}
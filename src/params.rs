@@ -0,0 +1,260 @@
+use crate::bounds::{Bounds, BoundsMode};
+use crate::obstacles::Obstacle;
+use crate::species::{default_species, Species};
+use crate::steering::SteeringMode;
+use rand::random;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+pub const DEFAULT_CONFIG_PATH: &str = "aquarium.toml";
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimParam {
+    pub pl_sprite: String,
+    pub pl_mass: f64,
+    pub pl_pop: f64,
+    pub pl_spread: f64,
+    pub pl_spreading_radius: f64,
+
+    pub steering_mode: SteeringMode,
+    pub avoid_w: f64,
+    pub feeler_length: f64,
+
+    pub bounds_mode: BoundsMode,
+    pub world_width: f64,
+    pub world_height: f64,
+    pub bounds_margin: f64,
+
+    pub mutation_rate: f64,
+    pub mutation_sigma: f64,
+    pub brain_sigma: f64,
+
+    pub seed: i64,
+    pub log_path: String,
+    pub log_interval: u64,
+    pub trait_log_path: String,
+    pub snapshot_path: String,
+    pub graph_seconds: u32,
+    pub frames_path: String,
+    pub frames_interval: u64,
+
+    pub obstacles: Vec<Obstacle>,
+    pub species: Vec<Species>,
+
+    #[serde(skip)]
+    pub ready: bool,
+}
+
+impl Default for SimParam {
+    fn default() -> Self {
+        SimParam {
+            pl_sprite: String::from("assets/seaweed.png"),
+            pl_mass: 250.0,
+            pl_pop: 10.0,
+            pl_spread: 500.0,
+            pl_spreading_radius: 250.0,
+
+            steering_mode: SteeringMode::Weighted,
+            avoid_w: 3.0,
+            feeler_length: 80.0,
+
+            bounds_mode: BoundsMode::None,
+            world_width: 1820.0,
+            world_height: 1080.0,
+            bounds_margin: 100.0,
+
+            mutation_rate: 0.5,
+            mutation_sigma: 0.05,
+            brain_sigma: 0.1,
+
+            seed: random::<u32>() as i64,
+            log_path: String::new(),
+            log_interval: 60,
+            trait_log_path: String::new(),
+            snapshot_path: String::new(),
+            graph_seconds: 60,
+            frames_path: String::new(),
+            frames_interval: 60,
+            obstacles: vec![],
+            species: default_species(),
+
+            ready: false,
+        }
+    }
+}
+
+fn legacy_field(name: &str) -> Option<(&str, &str)> {
+    let (species, field) = match name.split_at_checked(3)? {
+        ("pr_", field) => ("preys", field),
+        ("pd_", field) => ("predators", field),
+        _ => return None,
+    };
+
+    match field {
+        "repro_mass" => Some((species, "repro_growth")),
+        field => Some((species, field)),
+    }
+}
+
+fn species_table<'a>(
+    table: &'a mut toml::Table,
+    name: &str,
+) -> Result<&'a mut toml::Table, String> {
+    table
+        .get_mut("species")
+        .and_then(|species| species.as_array_mut())
+        .and_then(|species| {
+            species
+                .iter_mut()
+                .filter_map(|s| s.as_table_mut())
+                .find(|s| s.get("name").and_then(|n| n.as_str()) == Some(name))
+        })
+        .ok_or(format!("espécie desconhecida: {name}"))
+}
+
+fn migrate_legacy(table: &mut toml::Table) -> Result<(), String> {
+    let legacy: Vec<String> = table
+        .keys()
+        .filter(|key| legacy_field(key).is_some())
+        .cloned()
+        .collect();
+    let values: Vec<toml::Value> = legacy.iter().filter_map(|key| table.remove(key)).collect();
+
+    let has_species = table
+        .get("species")
+        .and_then(|species| species.as_array())
+        .is_some_and(|species| !species.is_empty());
+    if has_species {
+        return Ok(());
+    }
+
+    let species = toml::Value::try_from(default_species()).map_err(|e| e.to_string())?;
+    table.insert(String::from("species"), species);
+    for (key, value) in legacy.iter().zip(values) {
+        let (species, field) = legacy_field(key).unwrap();
+        species_table(table, species)?.insert(field.to_string(), value);
+    }
+
+    Ok(())
+}
+
+impl SimParam {
+    pub fn bounds(&self) -> Bounds {
+        Bounds {
+            mode: self.bounds_mode,
+            width: self.world_width,
+            height: self.world_height,
+            margin: self.bounds_margin,
+        }
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+        let mut table: toml::Table = toml::from_str(&text).map_err(|e| format!("{path}: {e}"))?;
+        migrate_legacy(&mut table).map_err(|e| format!("{path}: {e}"))?;
+        table.try_into().map_err(|e| format!("{path}: {e}"))
+    }
+
+    pub fn load_or_default(path: &str) -> Result<Self, String> {
+        if fs::metadata(path).is_err() {
+            return Ok(Self::default());
+        }
+
+        Self::load(path)
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        let mut table = toml::Table::try_from(&*self).map_err(|e| e.to_string())?;
+        let (target, key) = match legacy_field(name).or_else(|| name.split_once('.')) {
+            Some((species, field)) => (species_table(&mut table, species)?, field),
+            None => (&mut table, name),
+        };
+        let new_value = match target.get(key) {
+            None => return Err(format!("parâmetro desconhecido: {name}")),
+            Some(toml::Value::Float(_)) => value.parse().map(toml::Value::Float).ok(),
+            Some(toml::Value::Integer(_)) => value.parse().map(toml::Value::Integer).ok(),
+            Some(toml::Value::Boolean(_)) => value.parse().map(toml::Value::Boolean).ok(),
+            Some(toml::Value::String(_)) => Some(toml::Value::String(value.to_string())),
+            Some(_) => None,
+        }
+        .ok_or(format!("valor inválido para {name}: {value}"))?;
+        target.insert(key.to_string(), new_value);
+
+        let ready = self.ready;
+        *self = table.try_into().map_err(|e| format!("{name}: {e}"))?;
+        self.ready = ready;
+
+        Ok(())
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let text = toml::to_string_pretty(self).map_err(|e| format!("{path}: {e}"))?;
+        fs::write(path, text).map_err(|e| format!("{path}: {e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_round_trips_each_type() {
+        let mut p = SimParam::default();
+        p.set("pl_pop", "42.5").unwrap();
+        p.set("log_interval", "7").unwrap();
+        p.set("seed", "-3").unwrap();
+        p.set("log_path", "runs/a.csv").unwrap();
+        p.set("bounds_mode", "wrap").unwrap();
+
+        assert_eq!(p.pl_pop, 42.5);
+        assert_eq!(p.log_interval, 7);
+        assert_eq!(p.seed, -3);
+        assert_eq!(p.log_path, "runs/a.csv");
+        assert!(p.bounds_mode == BoundsMode::Wrap);
+    }
+
+    #[test]
+    fn set_reaches_species_fields() {
+        let mut p = SimParam::default();
+        p.set("predators.litter", "3").unwrap();
+        p.set("preys.brain", "true").unwrap();
+        p.set("pr_pop", "20").unwrap();
+        p.set("pd_repro_mass", "1.5").unwrap();
+
+        assert_eq!(p.species[1].litter, 3);
+        assert!(p.species[0].brain);
+        assert_eq!(p.species[0].pop, 20.0);
+        assert_eq!(p.species[1].repro_growth, 1.5);
+    }
+
+    #[test]
+    fn set_keeps_ready() {
+        let mut p = SimParam {
+            ready: true,
+            ..SimParam::default()
+        };
+        p.set("pl_mass", "100").unwrap();
+        assert!(p.ready);
+    }
+
+    #[test]
+    fn set_rejects_unknown_names() {
+        let mut p = SimParam::default();
+        assert!(p.set("nope", "1").is_err());
+        assert!(p.set("preys.nope", "1").is_err());
+        assert!(p.set("tunas.pop", "1").is_err());
+        assert!(p.set("pr_nope", "1").is_err());
+    }
+
+    #[test]
+    fn set_rejects_invalid_values() {
+        let mut p = SimParam::default();
+        assert!(p.set("pl_pop", "muitas").is_err());
+        assert!(p.set("log_interval", "1.5").is_err());
+        assert!(p.set("preys.brain", "talvez").is_err());
+        assert!(p.set("bounds_mode", "sideways").is_err());
+        assert!(p.set("obstacles", "[]").is_err());
+        assert_eq!(p.pl_pop, SimParam::default().pl_pop);
+    }
+}
@@ -0,0 +1,233 @@
+use crate::bodies::Position;
+use crate::fishes::Fish;
+use crate::genome::TRAIT_NAMES;
+use crate::params::SimParam;
+use crate::species::{SpeciesRegistry, PLANTS};
+use crate::world::World;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn csv_row<S: AsRef<str>>(fields: &[S]) -> String {
+    fields
+        .iter()
+        .map(|field| csv_field(field.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+struct GroupStats {
+    count: usize,
+    mean_mass: f64,
+    mean_speed: f64,
+    biomass: f64,
+}
+
+impl GroupStats {
+    fn collect<I: Iterator<Item = (f64, f64)>>(samples: I) -> Self {
+        let mut count = 0;
+        let mut biomass = 0.0;
+        let mut speed_sum = 0.0;
+        for (mass, speed) in samples {
+            count += 1;
+            biomass += mass;
+            speed_sum += speed;
+        }
+
+        let (mean_mass, mean_speed) = if count == 0 {
+            (0.0, 0.0)
+        } else {
+            (biomass / count as f64, speed_sum / count as f64)
+        };
+
+        Self {
+            count,
+            mean_mass,
+            mean_speed,
+            biomass,
+        }
+    }
+}
+
+pub struct PopulationRecorder {
+    writer: BufWriter<File>,
+    interval: u64,
+}
+
+impl PopulationRecorder {
+    pub fn create(path: &str, interval: u64, species: &SpeciesRegistry) -> Result<Self, String> {
+        let file = File::create(path).map_err(|e| format!("{path}: {e}"))?;
+        let mut recorder = Self {
+            writer: BufWriter::new(file),
+            interval: interval.max(1),
+        };
+
+        let mut header = String::from("tick");
+        let groups = std::iter::once(PLANTS).chain(species.iter().map(|s| s.name.as_str()));
+        for group in groups {
+            for column in ["count", "mean_mass", "mean_speed", "biomass"] {
+                header += &format!(",{}", csv_field(&format!("{group}_{column}")));
+            }
+        }
+        writeln!(recorder.writer, "{header}").map_err(|e| e.to_string())?;
+
+        Ok(recorder)
+    }
+
+    pub fn record(&mut self, world: &World) -> Result<(), String> {
+        if !world.ticks().is_multiple_of(self.interval) {
+            return Ok(());
+        }
+
+        let mut groups = vec![GroupStats::collect(
+            world.plants.iter().map(|p| (p.mass(), p.vel().length())),
+        )];
+        groups.extend(world.fishes.iter().map(|fishes| {
+            GroupStats::collect(fishes.iter().map(|f| (f.mass(), f.vel().length())))
+        }));
+
+        let mut row = format!("{}", world.ticks());
+        for stats in groups {
+            row += &format!(
+                ",{},{},{},{}",
+                stats.count, stats.mean_mass, stats.mean_speed, stats.biomass
+            );
+        }
+        writeln!(self.writer, "{row}").map_err(|e| e.to_string())
+    }
+
+    pub fn flush(&mut self) -> Result<(), String> {
+        self.writer.flush().map_err(|e| e.to_string())
+    }
+}
+
+#[derive(Default)]
+struct GenerationStats {
+    count: usize,
+    sums: [f64; TRAIT_NAMES.len()],
+    squares: [f64; TRAIT_NAMES.len()],
+}
+
+pub struct TraitRecorder {
+    writer: BufWriter<File>,
+    interval: u64,
+}
+
+impl TraitRecorder {
+    pub fn create(path: &str, interval: u64) -> Result<Self, String> {
+        let file = File::create(path).map_err(|e| format!("{path}: {e}"))?;
+        let mut recorder = Self {
+            writer: BufWriter::new(file),
+            interval: interval.max(1),
+        };
+
+        let mut header = String::from("tick,group,generation,count");
+        for name in TRAIT_NAMES {
+            header += &format!(",{name}_mean,{name}_std");
+        }
+        writeln!(recorder.writer, "{header}").map_err(|e| e.to_string())?;
+
+        Ok(recorder)
+    }
+
+    fn record_group(&mut self, tick: u64, group: &str, fishes: &[Fish]) -> Result<(), String> {
+        let mut generations: BTreeMap<u32, GenerationStats> = BTreeMap::new();
+        for fish in fishes {
+            let stats = generations.entry(fish.generation()).or_default();
+            stats.count += 1;
+            for (i, value) in fish.genome().traits().into_iter().enumerate() {
+                stats.sums[i] += value;
+                stats.squares[i] += value * value;
+            }
+        }
+
+        for (generation, stats) in generations {
+            let n = stats.count as f64;
+            let mut row = format!("{tick},{},{generation},{}", csv_field(group), stats.count);
+            for i in 0..TRAIT_NAMES.len() {
+                let mean = stats.sums[i] / n;
+                let variance = (stats.squares[i] / n - mean * mean).max(0.0);
+                row += &format!(",{mean},{}", variance.sqrt());
+            }
+            writeln!(self.writer, "{row}").map_err(|e| e.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, world: &World) -> Result<(), String> {
+        if !world.ticks().is_multiple_of(self.interval) {
+            return Ok(());
+        }
+
+        for (species, fishes) in world.species().iter().zip(&world.fishes) {
+            self.record_group(world.ticks(), &species.name, fishes)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), String> {
+        self.writer.flush().map_err(|e| e.to_string())
+    }
+}
+
+pub struct Recorders {
+    population: Option<PopulationRecorder>,
+    traits: Option<TraitRecorder>,
+}
+
+impl Recorders {
+    pub fn create(parameters: &SimParam, world: &World) -> Result<Self, String> {
+        let population = if parameters.log_path.is_empty() {
+            None
+        } else {
+            Some(PopulationRecorder::create(
+                &parameters.log_path,
+                parameters.log_interval,
+                world.species(),
+            )?)
+        };
+
+        let traits = if parameters.trait_log_path.is_empty() {
+            None
+        } else {
+            Some(TraitRecorder::create(
+                &parameters.trait_log_path,
+                parameters.log_interval,
+            )?)
+        };
+
+        Ok(Self { population, traits })
+    }
+
+    pub fn record(&mut self, world: &World) -> Result<(), String> {
+        if let Some(recorder) = self.population.as_mut() {
+            recorder.record(world)?;
+        }
+        if let Some(recorder) = self.traits.as_mut() {
+            recorder.record(world)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), String> {
+        if let Some(recorder) = self.population.as_mut() {
+            recorder.flush()?;
+        }
+        if let Some(recorder) = self.traits.as_mut() {
+            recorder.flush()?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use crate::bounds::BoundsMode;
+use crate::camera::Camera;
+use crate::vectors::Vector2;
+use crate::world::World;
+use sdl2::image::LoadTexture;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{Canvas, RenderTarget, Texture, TextureCreator};
+
+pub fn load_textures<'a, C>(
+    tex_creator: &'a TextureCreator<C>,
+    world: &World,
+) -> Result<Vec<Texture<'a>>, String> {
+    std::iter::once(world.params().pl_sprite.as_str())
+        .chain(world.species().iter().map(|s| s.sprite.as_str()))
+        .map(|path| {
+            tex_creator
+                .load_texture(path)
+                .map_err(|e| format!("{path}: {e}"))
+        })
+        .collect()
+}
+
+pub fn render<T: RenderTarget>(
+    canvas: &mut Canvas<T>,
+    world: &mut World,
+    textures: &[Texture],
+    camera: &Camera,
+    debug: bool,
+) {
+    let bounds = world.bounds();
+    if bounds.mode != BoundsMode::None {
+        let (x, y) = camera.world_to_screen(Vector2::default()).get_components();
+        canvas.set_draw_color(Color::RGB(40, 60, 90));
+        canvas
+            .draw_rect(Rect::new(
+                x as i32,
+                y as i32,
+                (bounds.width * camera.zoom) as u32,
+                (bounds.height * camera.zoom) as u32,
+            ))
+            .unwrap();
+    }
+    for obstacle in world.obstacles() {
+        obstacle.draw(canvas, camera);
+    }
+    for plant in world.plants.iter_mut() {
+        plant.draw(canvas, &textures[0], camera, debug);
+    }
+    for (fishes, texture) in world.fishes.iter_mut().zip(&textures[1..]) {
+        for fish in fishes.iter_mut() {
+            fish.draw(canvas, texture, camera, debug);
+        }
+    }
+}
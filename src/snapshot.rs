@@ -0,0 +1,45 @@
+use crate::camera::Camera;
+use crate::world::World;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+
+const SNAPSHOT_VERSION: u32 = 9;
+
+#[derive(Serialize, Deserialize)]
+struct Snapshot<W> {
+    version: u32,
+    camera: Camera,
+    world: W,
+}
+
+pub fn save(path: &str, world: &World, camera: Camera) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| format!("{path}: {e}"))?;
+    let snapshot = Snapshot {
+        version: SNAPSHOT_VERSION,
+        camera,
+        world,
+    };
+
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &snapshot).map_err(|e| format!("{path}: {e}"))?;
+    writer.flush().map_err(|e| format!("{path}: {e}"))
+}
+
+pub fn load(path: &str) -> Result<(World, Camera), String> {
+    let file = File::open(path).map_err(|e| format!("{path}: {e}"))?;
+    let value: serde_json::Value =
+        serde_json::from_reader(BufReader::new(file)).map_err(|e| format!("{path}: {e}"))?;
+
+    let version = value.get("version").and_then(|v| v.as_u64());
+    if version != Some(SNAPSHOT_VERSION as u64) {
+        return Err(format!(
+            "{path}: versão de snapshot {version:?} não suportada (esperada {SNAPSHOT_VERSION})"
+        ));
+    }
+
+    let snapshot: Snapshot<World> =
+        serde_json::from_value(value).map_err(|e| format!("{path}: {e}"))?;
+
+    Ok((snapshot.world, snapshot.camera))
+}
@@ -0,0 +1,227 @@
+use crate::bodies::Position;
+use crate::vectors::Vector2;
+
+const MAX_CELLS: i64 = 1 << 16;
+
+pub struct SpatialGrid {
+    cell_size: f64,
+    cell_dims: (f64, f64),
+    wrap: Option<(i32, i32)>,
+    origin: (i32, i32),
+    extent: (i32, i32),
+    starts: Vec<usize>,
+    entries: Vec<usize>,
+}
+
+fn axis_cells(
+    lo: i32,
+    hi: i32,
+    wrap: Option<i32>,
+    (start, len): (i32, i32),
+) -> impl Iterator<Item = i32> + Clone {
+    let (lo, hi) = match wrap {
+        Some(count) => (lo, hi.min(lo + count - 1)),
+        None => (lo.max(start), hi.min(start + len - 1)),
+    };
+
+    (lo..=hi)
+        .map(move |c| match wrap {
+            Some(count) => c.rem_euclid(count),
+            None => c,
+        })
+        .filter(move |c| (start..start + len).contains(c))
+}
+
+impl SpatialGrid {
+    pub fn new(cell_size: f64) -> Self {
+        Self {
+            cell_size,
+            cell_dims: (cell_size, cell_size),
+            wrap: None,
+            origin: (0, 0),
+            extent: (0, 0),
+            starts: vec![0],
+            entries: vec![],
+        }
+    }
+
+    fn cell_of(&self, point: Vector2) -> (i32, i32) {
+        let (x, y) = point.get_components();
+        (
+            (x / self.cell_dims.0).floor() as i32,
+            (y / self.cell_dims.1).floor() as i32,
+        )
+    }
+
+    fn wrapped_cell_of(&self, point: Vector2) -> (i32, i32) {
+        let (cx, cy) = self.cell_of(point);
+        match self.wrap {
+            Some((columns, rows)) => (cx.rem_euclid(columns), cy.rem_euclid(rows)),
+            None => (cx, cy),
+        }
+    }
+
+    fn set_wrap(&mut self, wrap: Option<(f64, f64)>, cell_size: f64) {
+        match wrap {
+            None => {
+                self.cell_dims = (cell_size, cell_size);
+                self.wrap = None;
+            }
+            Some((width, height)) => {
+                let columns = (width / cell_size).ceil().max(1.0);
+                let rows = (height / cell_size).ceil().max(1.0);
+                self.cell_dims = (width / columns, height / rows);
+                self.wrap = Some((columns as i32, rows as i32));
+            }
+        }
+    }
+
+    fn cell_index(&self, (x, y): (i32, i32)) -> usize {
+        ((y - self.origin.1) * self.extent.0 + (x - self.origin.0)) as usize
+    }
+
+    pub fn rebuild<T: Position>(&mut self, items: &[T], wrap: Option<(f64, f64)>) {
+        let mut cell_size = self.cell_size;
+        let cells = loop {
+            self.set_wrap(wrap, cell_size);
+            let cells: Vec<(i32, i32)> = items
+                .iter()
+                .map(|item| self.wrapped_cell_of(item.pos()))
+                .collect();
+            let (mut lo, mut hi) = ((i32::MAX, i32::MAX), (i32::MIN, i32::MIN));
+            for &(x, y) in &cells {
+                lo = (lo.0.min(x), lo.1.min(y));
+                hi = (hi.0.max(x), hi.1.max(y));
+            }
+            if cells.is_empty() {
+                (lo, hi) = ((0, 0), (-1, -1));
+            }
+
+            let columns = hi.0 as i64 - lo.0 as i64 + 1;
+            let rows = hi.1 as i64 - lo.1 as i64 + 1;
+            if columns * rows <= MAX_CELLS {
+                self.origin = lo;
+                self.extent = (columns as i32, rows as i32);
+                break cells;
+            }
+            cell_size *= 2.0;
+        };
+
+        self.starts.clear();
+        self.starts
+            .resize((self.extent.0 * self.extent.1) as usize + 1, 0);
+        for &cell in &cells {
+            let index = self.cell_index(cell);
+            self.starts[index + 1] += 1;
+        }
+        for i in 1..self.starts.len() {
+            self.starts[i] += self.starts[i - 1];
+        }
+
+        let mut next = self.starts.clone();
+        self.entries.clear();
+        self.entries.resize(items.len(), 0);
+        for (i, &cell) in cells.iter().enumerate() {
+            let index = self.cell_index(cell);
+            self.entries[next[index]] = i;
+            next[index] += 1;
+        }
+    }
+
+    pub fn query(&self, center: Vector2, radius: f64) -> impl Iterator<Item = usize> + '_ {
+        let (x0, y0) = self.cell_of(center - Vector2::new(radius, radius));
+        let (x1, y1) = self.cell_of(center + Vector2::new(radius, radius));
+        let columns = axis_cells(
+            x0,
+            x1,
+            self.wrap.map(|w| w.0),
+            (self.origin.0, self.extent.0),
+        );
+        let rows = axis_cells(
+            y0,
+            y1,
+            self.wrap.map(|w| w.1),
+            (self.origin.1, self.extent.1),
+        );
+
+        columns
+            .flat_map(move |x| rows.clone().map(move |y| (x, y)))
+            .map(|cell| self.cell_index(cell))
+            .flat_map(|index| {
+                self.entries[self.starts[index]..self.starts[index + 1]]
+                    .iter()
+                    .copied()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Point(Vector2);
+
+    impl Position for Point {
+        fn pos(&self) -> Vector2 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn query_covers_radius_without_wrap() {
+        let items = [
+            Point(Vector2::new(5.0, 5.0)),
+            Point(Vector2::new(25.0, 5.0)),
+            Point(Vector2::new(100.0, 100.0)),
+        ];
+        let mut grid = SpatialGrid::new(10.0);
+        grid.rebuild(&items, None);
+
+        let mut found: Vec<usize> = grid.query(Vector2::new(10.0, 5.0), 16.0).collect();
+        found.sort();
+        assert_eq!(found, vec![0, 1]);
+    }
+
+    #[test]
+    fn wrapped_grid_finds_items_on_the_far_edge() {
+        let items = [
+            Point(Vector2::new(100.0, 50.0)),
+            Point(Vector2::new(99.0, 1.0)),
+            Point(Vector2::new(50.0, 25.0)),
+        ];
+        let mut grid = SpatialGrid::new(10.0);
+        grid.rebuild(&items, Some((100.0, 50.0)));
+
+        let mut found: Vec<usize> = grid.query(Vector2::new(1.0, 1.0), 2.0).collect();
+        found.sort();
+        assert_eq!(found, vec![0, 1]);
+    }
+
+    #[test]
+    fn empty_grid_finds_nothing() {
+        let mut grid = SpatialGrid::new(10.0);
+        grid.rebuild::<Point>(&[], None);
+        assert_eq!(grid.query(Vector2::new(0.0, 0.0), 100.0).count(), 0);
+
+        grid.rebuild::<Point>(&[], Some((100.0, 50.0)));
+        assert_eq!(grid.query(Vector2::new(0.0, 0.0), 100.0).count(), 0);
+    }
+
+    #[test]
+    fn far_apart_items_stay_reachable() {
+        let items = [
+            Point(Vector2::new(-1.0e7, 3.0)),
+            Point(Vector2::new(2.0, 3.0)),
+            Point(Vector2::new(1.0e7, 1.0e7)),
+        ];
+        let mut grid = SpatialGrid::new(10.0);
+        grid.rebuild(&items, None);
+
+        let near: Vec<usize> = grid.query(Vector2::new(0.0, 0.0), 5.0).collect();
+        assert!(near.contains(&1));
+        let far: Vec<usize> = grid.query(Vector2::new(1.0e7, 1.0e7), 1.0).collect();
+        assert!(far.contains(&2));
+        let everything = grid.query(Vector2::new(0.0, 0.0), 2.0e7).count();
+        assert_eq!(everything, 3);
+    }
+}
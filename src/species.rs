@@ -0,0 +1,281 @@
+use crate::fishes::Reproduction;
+use crate::genome::Genome;
+use serde::{Deserialize, Serialize};
+
+pub const PLANTS: &str = "plants";
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Species {
+    pub name: String,
+    pub label: String,
+    pub sprite: String,
+    pub eats: Vec<String>,
+    pub pop: f64,
+    pub spread: f64,
+    pub mass: f64,
+    pub vis_a: f64,
+    pub vis_d: f64,
+    pub p_speed: f64,
+    pub max_force: f64,
+    pub separation_w: f64,
+    pub cohesion_w: f64,
+    pub alignment_w: f64,
+    pub flee_w: f64,
+    pub arrive_w: f64,
+    pub pursuit_w: f64,
+    pub wander_w: f64,
+    pub think_w: f64,
+    pub flock_w: f64,
+    pub bite: f64,
+    pub basal_cost: f64,
+    pub swim_cost: f64,
+    pub assimilation: f64,
+    pub repro_growth: f64,
+    pub repro_energy: f64,
+    pub litter: u32,
+    pub gestation: u32,
+    pub birth_cost: f64,
+    pub brain: bool,
+    pub brain_path: String,
+}
+
+impl Default for Species {
+    fn default() -> Self {
+        Species {
+            name: String::new(),
+            label: String::new(),
+            sprite: String::from("assets/fish.png"),
+            eats: vec![],
+            pop: 5.0,
+            spread: 300.0,
+            mass: 200.0,
+            vis_a: 179.0,
+            vis_d: 800.0,
+            p_speed: 5.0,
+            max_force: 10.0,
+            separation_w: 1.0,
+            cohesion_w: 1.0,
+            alignment_w: 1.0,
+            flee_w: 2.0,
+            arrive_w: 1.0,
+            pursuit_w: 1.0,
+            wander_w: 1.0,
+            think_w: 1.0,
+            flock_w: 1.0,
+            bite: 0.05,
+            basal_cost: 0.01,
+            swim_cost: 0.005,
+            assimilation: 0.2,
+            repro_growth: 1.2,
+            repro_energy: 0.7,
+            litter: 2,
+            gestation: 600,
+            birth_cost: 0.3,
+            brain: false,
+            brain_path: String::new(),
+        }
+    }
+}
+
+pub fn default_species() -> Vec<Species> {
+    vec![
+        Species {
+            name: String::from("preys"),
+            label: String::from("Presas"),
+            eats: vec![String::from(PLANTS)],
+            ..Species::default()
+        },
+        Species {
+            name: String::from("predators"),
+            label: String::from("Predadores"),
+            sprite: String::from("assets/shark.png"),
+            eats: vec![String::from("preys")],
+            pop: 2.0,
+            spread: 500.0,
+            mass: 800.0,
+            vis_a: 120.0,
+            vis_d: 1000.0,
+            p_speed: 6.0,
+            separation_w: 0.0,
+            cohesion_w: 0.0,
+            alignment_w: 0.0,
+            assimilation: 0.1,
+            repro_growth: 1.125,
+            litter: 1,
+            gestation: 1800,
+            birth_cost: 0.25,
+            ..Species::default()
+        },
+    ]
+}
+
+impl Species {
+    pub fn label(&self) -> &str {
+        if self.label.is_empty() {
+            &self.name
+        } else {
+            &self.label
+        }
+    }
+
+    pub fn genome(&self) -> Genome {
+        Genome {
+            mass: self.mass / 10.0,
+            vision_angle: self.vis_a,
+            vision_depth: self.vis_d,
+            peak_speed: self.p_speed,
+            max_force: self.max_force,
+            separation_w: self.separation_w,
+            cohesion_w: self.cohesion_w,
+            alignment_w: self.alignment_w,
+            brain: None,
+        }
+    }
+
+    pub fn reproduction(
+        &self,
+        mutation_rate: f64,
+        mutation_sigma: f64,
+        brain_sigma: f64,
+    ) -> Reproduction {
+        Reproduction {
+            growth: self.repro_growth,
+            energy: self.repro_energy,
+            litter: self.litter,
+            gestation: self.gestation,
+            cost: self.birth_cost,
+            mutation_rate,
+            mutation_sigma,
+            brain_sigma,
+        }
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Diet {
+    pub plants: bool,
+    pub prey: Vec<usize>,
+    pub predators: Vec<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SpeciesRegistry {
+    species: Vec<Species>,
+    diets: Vec<Diet>,
+}
+
+impl SpeciesRegistry {
+    pub fn new(species: Vec<Species>) -> Result<Self, String> {
+        let mut diets = vec![Diet::default(); species.len()];
+        for (i, s) in species.iter().enumerate() {
+            if s.name.is_empty() || s.name == PLANTS {
+                return Err(format!("nome de espécie inválido: \"{}\"", s.name));
+            }
+            if species[..i].iter().any(|other| other.name == s.name) {
+                return Err(format!("espécie duplicada: {}", s.name));
+            }
+
+            for food in &s.eats {
+                if food == PLANTS {
+                    diets[i].plants = true;
+                    continue;
+                }
+
+                let prey = species
+                    .iter()
+                    .position(|other| &other.name == food)
+                    .ok_or(format!("{}: espécie desconhecida na dieta: {food}", s.name))?;
+                if prey == i {
+                    return Err(format!("{}: uma espécie não pode comer a si mesma", s.name));
+                }
+                if !diets[i].prey.contains(&prey) {
+                    diets[i].prey.push(prey);
+                    diets[prey].predators.push(i);
+                }
+            }
+        }
+
+        Ok(Self { species, diets })
+    }
+
+    pub fn count(&self) -> usize {
+        self.species.len()
+    }
+
+    pub fn get(&self, index: usize) -> &Species {
+        &self.species[index]
+    }
+
+    pub fn diet(&self, index: usize) -> &Diet {
+        &self.diets[index]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Species> {
+        self.species.iter()
+    }
+
+    pub fn grazers(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.count()).filter(|&i| self.diets[i].plants)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn species(name: &str, eats: &[&str]) -> Species {
+        Species {
+            name: name.to_string(),
+            eats: eats.iter().map(|food| food.to_string()).collect(),
+            ..Species::default()
+        }
+    }
+
+    #[test]
+    fn diets_link_prey_and_predators() {
+        let registry = SpeciesRegistry::new(vec![
+            species("sardines", &[PLANTS]),
+            species("tunas", &["sardines", "sardines"]),
+            species("sharks", &["tunas", "sardines"]),
+        ])
+        .unwrap();
+
+        assert!(registry.diet(0).plants);
+        assert!(!registry.diet(1).plants);
+        assert_eq!(registry.diet(0).predators, vec![1, 2]);
+        assert_eq!(registry.diet(1).prey, vec![0]);
+        assert_eq!(registry.diet(2).prey, vec![1, 0]);
+        assert_eq!(registry.grazers().collect::<Vec<_>>(), vec![0]);
+    }
+
+    #[test]
+    fn default_species_are_valid() {
+        let registry = SpeciesRegistry::new(default_species()).unwrap();
+        assert_eq!(registry.diet(1).prey, vec![0]);
+    }
+
+    #[test]
+    fn rejects_duplicate_names() {
+        let result = SpeciesRegistry::new(vec![species("a", &[]), species("a", &[])]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rejects_reserved_and_empty_names() {
+        assert!(SpeciesRegistry::new(vec![species(PLANTS, &[])]).is_err());
+        assert!(SpeciesRegistry::new(vec![species("", &[])]).is_err());
+    }
+
+    #[test]
+    fn rejects_eating_itself() {
+        let result = SpeciesRegistry::new(vec![species("a", &["a"])]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_food() {
+        let result = SpeciesRegistry::new(vec![species("a", &["b"])]);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,125 @@
+use crate::vectors::Vector2;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SteeringMode {
+    Weighted,
+    Prioritized,
+}
+
+#[derive(Default)]
+pub struct Steering {
+    forces: Vec<(Vector2, f64)>,
+    clamp_speed: f64,
+    tagged: bool,
+    last_force: Option<Vector2>,
+}
+
+impl Steering {
+    pub fn add(&mut self, force: Vector2, weight: f64) {
+        if weight > 0.0 {
+            self.forces.push((force, weight));
+        }
+    }
+
+    pub fn request_speed(&mut self, clamp_speed: f64) {
+        self.clamp_speed = self.clamp_speed.max(clamp_speed);
+    }
+
+    pub fn clamp_speed(&self) -> f64 {
+        self.clamp_speed
+    }
+
+    pub fn last_force(&self) -> Vector2 {
+        self.last_force.unwrap_or(Vector2::default())
+    }
+
+    pub fn tag(&mut self) -> bool {
+        !std::mem::replace(&mut self.tagged, true)
+    }
+
+    pub fn resolve(&mut self, mode: SteeringMode, max_force: f64) -> Vector2 {
+        let mut total = Vector2::default();
+        match mode {
+            SteeringMode::Weighted => {
+                for &(force, weight) in &self.forces {
+                    total += force * weight;
+                }
+                total %= max_force;
+            }
+            SteeringMode::Prioritized => {
+                for &(force, weight) in &self.forces {
+                    let remaining = max_force - total.length();
+                    if remaining <= 0.0 {
+                        break;
+                    }
+
+                    total += (force * weight) % remaining;
+                }
+            }
+        }
+
+        self.forces.clear();
+        self.clamp_speed = 0.0;
+        self.tagged = false;
+        self.last_force = Some(total);
+
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weighted_sums_then_truncates() {
+        let mut steering = Steering::default();
+        steering.add(Vector2::new(3.0, 0.0), 1.0);
+        steering.add(Vector2::new(0.0, 2.0), 2.0);
+        assert_eq!(
+            steering
+                .resolve(SteeringMode::Weighted, 2.5)
+                .get_components(),
+            (1.5, 2.0)
+        );
+    }
+
+    #[test]
+    fn prioritized_fills_the_budget_in_order() {
+        let mut steering = Steering::default();
+        steering.add(Vector2::new(6.0, 0.0), 1.0);
+        steering.add(Vector2::new(0.0, 8.0), 1.0);
+        assert_eq!(
+            steering
+                .resolve(SteeringMode::Prioritized, 10.0)
+                .get_components(),
+            (6.0, 4.0)
+        );
+
+        steering.add(Vector2::new(20.0, 0.0), 1.0);
+        steering.add(Vector2::new(0.0, 5.0), 1.0);
+        assert_eq!(
+            steering
+                .resolve(SteeringMode::Prioritized, 10.0)
+                .get_components(),
+            (10.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn resolve_ignores_zero_weights_and_resets() {
+        let mut steering = Steering::default();
+        steering.add(Vector2::new(4.0, 0.0), 0.0);
+        steering.request_speed(3.0);
+        assert!(steering.tag());
+        assert!(!steering.tag());
+
+        let force = steering.resolve(SteeringMode::Weighted, 10.0);
+        assert_eq!(force.get_components(), (0.0, 0.0));
+        assert_eq!(steering.last_force().get_components(), (0.0, 0.0));
+        assert_eq!(steering.clamp_speed(), 0.0);
+        assert!(steering.tag());
+    }
+}
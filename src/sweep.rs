@@ -0,0 +1,312 @@
+use crate::params::SimParam;
+use crate::recorder::csv_row;
+use crate::species::PLANTS;
+use crate::world::{World, TICK_RATE};
+use serde::Deserialize;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+const SAMPLE_INTERVAL: u64 = TICK_RATE as u64;
+
+fn default_output() -> String {
+    String::from("sweep.csv")
+}
+
+#[derive(Deserialize)]
+struct SweepSpec {
+    ticks: u64,
+    seeds: Vec<i64>,
+    #[serde(default = "default_output")]
+    output: String,
+    #[serde(default)]
+    threads: usize,
+    #[serde(default)]
+    params: toml::Table,
+}
+
+struct SweepRun {
+    values: Vec<String>,
+    seed: i64,
+}
+
+struct RunSummary {
+    ticks: u64,
+    finals: Vec<usize>,
+    peaks: Vec<usize>,
+    extinctions: Vec<Option<u64>>,
+    periods: Vec<Option<f64>>,
+}
+
+fn value_text(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn combinations(params: &toml::Table) -> Result<Vec<Vec<String>>, String> {
+    let mut combos = vec![vec![]];
+    for (name, values) in params {
+        let values = match values {
+            toml::Value::Array(values) if !values.is_empty() => values,
+            _ => return Err(format!("{name}: esperada uma lista de valores")),
+        };
+
+        combos = combos
+            .into_iter()
+            .flat_map(|combo| {
+                values.iter().map(move |value| {
+                    let mut combo = combo.clone();
+                    combo.push(value_text(value));
+                    combo
+                })
+            })
+            .collect();
+    }
+
+    Ok(combos)
+}
+
+fn oscillation_period(series: &[usize]) -> Option<f64> {
+    if series.is_empty() {
+        return None;
+    }
+
+    let mean = series.iter().sum::<usize>() as f64 / series.len() as f64;
+    let crossings: Vec<usize> = series
+        .windows(2)
+        .enumerate()
+        .filter(|(_, w)| (w[0] as f64) < mean && (w[1] as f64) >= mean)
+        .map(|(i, _)| i + 1)
+        .collect();
+    if crossings.len() < 2 {
+        return None;
+    }
+
+    let span = (crossings[crossings.len() - 1] - crossings[0]) as f64;
+    Some(span / (crossings.len() - 1) as f64 * SAMPLE_INTERVAL as f64 / TICK_RATE as f64)
+}
+
+fn simulate(parameters: &SimParam, ticks: u64) -> Result<RunSummary, String> {
+    let mut world = World::new(parameters)?;
+    let groups = world.fishes.len();
+    let mut peaks = vec![0; groups + 1];
+    let mut extinctions = vec![None; groups];
+    let mut series = vec![vec![]; groups];
+    let counts = |world: &World| {
+        let mut counts = vec![world.plants.len()];
+        counts.extend(world.fishes.iter().map(|fishes| fishes.len()));
+        counts
+    };
+
+    while world.ticks() < ticks {
+        world.step();
+
+        let counts = counts(&world);
+        for (peak, count) in peaks.iter_mut().zip(&counts) {
+            *peak = (*peak).max(*count);
+        }
+        for (extinction, count) in extinctions.iter_mut().zip(&counts[1..]) {
+            if extinction.is_none() && *count == 0 {
+                *extinction = Some(world.ticks());
+            }
+        }
+        if world.ticks().is_multiple_of(SAMPLE_INTERVAL) {
+            for (samples, count) in series.iter_mut().zip(&counts[1..]) {
+                samples.push(*count);
+            }
+        }
+
+        if counts[1..].iter().all(|&count| count == 0) {
+            break;
+        }
+    }
+
+    Ok(RunSummary {
+        ticks: world.ticks(),
+        finals: counts(&world),
+        peaks,
+        extinctions,
+        periods: series
+            .iter()
+            .map(|samples| oscillation_period(samples))
+            .collect(),
+    })
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn summary_row(
+    index: usize,
+    run: &SweepRun,
+    result: &Result<RunSummary, String>,
+    summary_columns: usize,
+) -> String {
+    let mut row = vec![index.to_string(), run.seed.to_string()];
+    row.extend(run.values.iter().cloned());
+    match result {
+        Ok(summary) => {
+            row.push(summary.ticks.to_string());
+            row.extend(summary.finals.iter().map(|c| c.to_string()));
+            row.extend(summary.peaks.iter().map(|c| c.to_string()));
+            row.extend(summary.extinctions.iter().map(|t| optional(*t)));
+            row.extend(
+                summary
+                    .periods
+                    .iter()
+                    .map(|p| optional(p.map(|p| format!("{p:.1}")))),
+            );
+            row.push(String::new());
+        }
+        Err(e) => {
+            row.extend(std::iter::repeat_n(String::new(), summary_columns));
+            row.push(e.clone());
+        }
+    }
+
+    csv_row(&row)
+}
+
+pub fn run(spec_path: &str, base: &SimParam) -> Result<(), String> {
+    let text = fs::read_to_string(spec_path).map_err(|e| format!("{spec_path}: {e}"))?;
+    let spec: SweepSpec = toml::from_str(&text).map_err(|e| format!("{spec_path}: {e}"))?;
+    if spec.seeds.is_empty() {
+        return Err(format!("{spec_path}: nenhuma semente definida"));
+    }
+
+    let names: Vec<&String> = spec.params.keys().collect();
+    let runs: Vec<SweepRun> = combinations(&spec.params)?
+        .into_iter()
+        .flat_map(|values| {
+            spec.seeds.iter().map(move |&seed| SweepRun {
+                values: values.clone(),
+                seed,
+            })
+        })
+        .collect();
+
+    let mut configs = Vec::with_capacity(runs.len());
+    for run in &runs {
+        let mut parameters = base.clone();
+        for (name, value) in names.iter().zip(&run.values) {
+            parameters.set(name, value)?;
+        }
+        parameters.seed = run.seed;
+        configs.push(parameters);
+    }
+
+    let species: Vec<String> = base.species.iter().map(|s| s.name.clone()).collect();
+    let groups: Vec<&str> = std::iter::once(PLANTS)
+        .chain(species.iter().map(|s| s.as_str()))
+        .collect();
+    let mut header: Vec<String> = ["run", "seed"]
+        .iter()
+        .map(|s| s.to_string())
+        .chain(names.iter().map(|s| s.to_string()))
+        .collect();
+    let summary_start = header.len();
+    header.push(String::from("ticks"));
+    header.extend(groups.iter().map(|g| format!("{g}_final")));
+    header.extend(groups.iter().map(|g| format!("{g}_peak")));
+    header.extend(species.iter().map(|s| format!("{s}_extinction")));
+    header.extend(species.iter().map(|s| format!("{s}_period")));
+    let summary_columns = header.len() - summary_start;
+    header.push(String::from("error"));
+
+    let file = File::create(&spec.output).map_err(|e| format!("{}: {e}", spec.output))?;
+    let mut writer = BufWriter::new(file);
+    writeln!(writer, "{}", csv_row(&header))
+        .and_then(|_| writer.flush())
+        .map_err(|e| format!("{}: {e}", spec.output))?;
+
+    let threads = match spec.threads {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    }
+    .min(runs.len());
+    println!("{} execuções em {} threads", runs.len(), threads);
+
+    let next = AtomicUsize::new(0);
+    let failures = AtomicUsize::new(0);
+    let writer = Mutex::new(writer);
+    let write_error: Mutex<Option<String>> = Mutex::new(None);
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= configs.len() {
+                    break;
+                }
+
+                let result =
+                    panic::catch_unwind(AssertUnwindSafe(|| simulate(&configs[i], spec.ticks)))
+                        .unwrap_or_else(|_| Err(String::from("a execução entrou em pânico")));
+                match &result {
+                    Ok(_) => println!("Execução {}/{} concluída", i + 1, configs.len()),
+                    Err(e) => {
+                        failures.fetch_add(1, Ordering::Relaxed);
+                        eprintln!("Execução {}/{} falhou: {e}", i + 1, configs.len());
+                    }
+                }
+
+                let row = summary_row(i, &runs[i], &result, summary_columns);
+                let mut writer = writer.lock().unwrap();
+                if let Err(e) = writeln!(writer, "{row}").and_then(|_| writer.flush()) {
+                    write_error
+                        .lock()
+                        .unwrap()
+                        .get_or_insert(format!("{}: {e}", spec.output));
+                }
+            });
+        }
+    });
+
+    if let Some(e) = write_error.into_inner().unwrap() {
+        return Err(e);
+    }
+    println!("Resumo salvo em {}", spec.output);
+
+    match failures.into_inner() {
+        0 => Ok(()),
+        n => Err(format!("{n} de {} execuções falharam", runs.len())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn oscillation_period_of_a_sine() {
+        let series: Vec<usize> = (0..200)
+            .map(|i| (100.0 + 50.0 * (2.0 * PI * i as f64 / 20.0).sin()).round() as usize)
+            .collect();
+        assert_eq!(oscillation_period(&series), Some(20.0));
+    }
+
+    #[test]
+    fn oscillation_period_needs_two_crossings() {
+        assert_eq!(oscillation_period(&[]), None);
+        assert_eq!(oscillation_period(&[5, 5, 5, 5]), None);
+        assert_eq!(oscillation_period(&[0, 10, 10, 0, 0]), None);
+    }
+
+    #[test]
+    fn combinations_form_the_cartesian_product() {
+        let params: toml::Table = toml::from_str("a = [1, 2]\nb = [\"x\", \"y\", \"z\"]").unwrap();
+        let combos = combinations(&params).unwrap();
+        assert_eq!(combos.len(), 6);
+        assert_eq!(combos[0], vec!["1", "x"]);
+        assert_eq!(combos[5], vec!["2", "z"]);
+
+        let params: toml::Table = toml::from_str("a = []").unwrap();
+        assert!(combinations(&params).is_err());
+    }
+}
@@ -1,8 +1,9 @@
-use rand::random;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::f64::consts::PI;
 use std::ops::{Add, AddAssign, Mul, Neg, Rem, RemAssign, Sub};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Vector2 {
     x: f64,
     y: f64,
@@ -17,9 +18,9 @@ impl Vector2 {
         Self { x: 0.0, y: 0.0 }
     }
 
-    pub fn random_in_radius(r: f64) -> Self {
-        let d = (random::<f64>()).sqrt() * r;
-        let thetha = (random::<f64>()) * 2.0 * PI;
+    pub fn random_in_radius<R: Rng>(r: f64, rng: &mut R) -> Self {
+        let d = (rng.gen::<f64>()).sqrt() * r;
+        let thetha = (rng.gen::<f64>()) * 2.0 * PI;
 
         Vector2::new(d * f64::cos(thetha), d * f64::sin(thetha))
     }
@@ -137,4 +138,4 @@ impl Neg for Vector2 {
     fn neg(self) -> Self::Output {
         self * -1.0
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,482 @@
+use crate::bodies::{Position, Vision};
+use crate::bounds::Bounds;
+use crate::brain::{self, Brain};
+use crate::fishes::{Fish, Plant};
+use crate::obstacles::{self, Obstacle};
+use crate::params::SimParam;
+use crate::spatial::SpatialGrid;
+use crate::species::{Species, SpeciesRegistry};
+use crate::vectors::Vector2;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+pub const TICK_RATE: u32 = 60;
+const GROW_INTERVAL: u64 = TICK_RATE as u64;
+const GRID_CELL_SIZE: f64 = 128.0;
+const SPAWN_ATTEMPTS: u32 = 100;
+
+struct WorldGrids {
+    plants: SpatialGrid,
+    fishes: Vec<SpatialGrid>,
+}
+
+impl Default for WorldGrids {
+    fn default() -> Self {
+        Self {
+            plants: SpatialGrid::new(GRID_CELL_SIZE),
+            fishes: vec![],
+        }
+    }
+}
+
+struct Senses {
+    plant: Option<Vector2>,
+    predator: Option<Vector2>,
+    prey: Option<Vector2>,
+}
+
+fn seed_brains<R: Rng>(path: &str, count: usize, rng: &mut R) -> Result<Vec<Brain>, String> {
+    if path.is_empty() {
+        return Ok((0..count).map(|_| Brain::random(rng)).collect());
+    }
+
+    let brains = brain::load_all(path)?;
+    Ok(brains.iter().cycle().take(count).cloned().collect())
+}
+
+fn spawn_point<R: Rng>(
+    bounds: &Bounds,
+    obstacles: &[Obstacle],
+    center: Vector2,
+    spread: f64,
+    rng: &mut R,
+) -> Result<Vector2, String> {
+    let free = |pos: Vector2| {
+        bounds.contains(pos) && !obstacles.iter().any(|obstacle| obstacle.contains(pos))
+    };
+    for _ in 0..SPAWN_ATTEMPTS {
+        let pos = center + Vector2::random_in_radius(spread, rng);
+        if free(pos) {
+            return Ok(pos);
+        }
+    }
+
+    if free(center) {
+        return Ok(center);
+    }
+    let (x, y) = center.get_components();
+    Err(format!(
+        "nenhuma posição livre em {SPAWN_ATTEMPTS} tentativas ao redor de ({x:.0}, {y:.0})"
+    ))
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct World {
+    pub plants: Vec<Plant>,
+    pub fishes: Vec<Vec<Fish>>,
+    species: SpeciesRegistry,
+    ticks: u64,
+    seed: i64,
+    rng: ChaCha8Rng,
+    params: SimParam,
+    #[serde(skip)]
+    grids: WorldGrids,
+}
+
+impl World {
+    pub fn new(parameters: &SimParam) -> Result<Self, String> {
+        let species = SpeciesRegistry::new(parameters.species.clone())?;
+        let mut world = Self {
+            plants: vec![],
+            fishes: (0..species.count()).map(|_| vec![]).collect(),
+            species,
+            ticks: 0,
+            seed: parameters.seed,
+            rng: ChaCha8Rng::seed_from_u64(parameters.seed as u64),
+            params: parameters.clone(),
+            grids: WorldGrids::default(),
+        };
+
+        let center = parameters.bounds().center();
+        for _i in 0..parameters.pl_pop as i32 {
+            let new_pos = spawn_point(
+                &parameters.bounds(),
+                &parameters.obstacles,
+                center,
+                parameters.pl_spread,
+                &mut world.rng,
+            )?;
+            world.plants.push(Plant::new(
+                new_pos,
+                parameters.pl_mass / 10.0,
+                parameters.pl_spreading_radius,
+                &mut world.rng,
+            ));
+        }
+
+        for (species, fishes) in world.species.iter().zip(world.fishes.iter_mut()) {
+            let mut brains = if species.brain {
+                seed_brains(&species.brain_path, species.pop as usize, &mut world.rng)?
+            } else {
+                vec![]
+            };
+            for _i in 0..species.pop as i32 {
+                let new_pos = spawn_point(
+                    &parameters.bounds(),
+                    &parameters.obstacles,
+                    center,
+                    species.spread,
+                    &mut world.rng,
+                )?;
+                let mut genome = species.genome();
+                genome.brain = brains.pop();
+                fishes.push(Fish::new(new_pos, genome, &mut world.rng));
+            }
+        }
+
+        Ok(world)
+    }
+
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    pub fn seed(&self) -> i64 {
+        self.seed
+    }
+
+    pub fn params(&self) -> &SimParam {
+        &self.params
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        self.params.bounds()
+    }
+
+    pub fn species(&self) -> &SpeciesRegistry {
+        &self.species
+    }
+
+    pub fn obstacles(&self) -> &[Obstacle] {
+        &self.params.obstacles
+    }
+
+    fn placeable(&self, pos: Vector2) -> Option<Vector2> {
+        let bounds = self.params.bounds();
+        let pos = match bounds.wrap_size() {
+            Some(_) => bounds.wrap(pos),
+            None => pos,
+        };
+
+        (bounds.contains(pos) && obstacles::escape_all(&self.params.obstacles, pos).is_none())
+            .then_some(pos)
+    }
+
+    pub fn spawn_plant(&mut self, pos: Vector2) {
+        if let Some(pos) = self.placeable(pos) {
+            let plant = Plant::new(
+                pos,
+                self.params.pl_mass / 10.0,
+                self.params.pl_spreading_radius,
+                &mut self.rng,
+            );
+            self.plants.push(plant);
+        }
+    }
+
+    pub fn spawn_fish(&mut self, index: usize, pos: Vector2) {
+        if let Some(pos) = self.placeable(pos) {
+            let species = self.species.get(index);
+            let mut genome = species.genome();
+            if species.brain {
+                genome.brain = Some(Brain::random(&mut self.rng));
+            }
+            self.fishes[index].push(Fish::new(pos, genome, &mut self.rng));
+        }
+    }
+
+    pub fn remove_at(&mut self, x: i32, y: i32) -> bool {
+        for fishes in self.fishes.iter_mut().rev() {
+            if let Some(i) = fishes.iter().rposition(|fish| fish.hit(x, y)) {
+                fishes.swap_remove(i);
+                return true;
+            }
+        }
+
+        match self.plants.iter().rposition(|plant| plant.hit(x, y)) {
+            Some(i) => {
+                self.plants.swap_remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn save_brains<F: Fn(&Species) -> String>(
+        &self,
+        path_for: F,
+    ) -> Result<Vec<String>, String> {
+        let mut paths = vec![];
+        for (species, fishes) in self.species.iter().zip(&self.fishes) {
+            let mut brains = fishes
+                .iter()
+                .filter_map(|fish| fish.genome().brain.as_ref())
+                .peekable();
+            if brains.peek().is_none() {
+                continue;
+            }
+
+            let path = path_for(species);
+            brain::save_all(&path, brains)?;
+            paths.push(path);
+        }
+
+        Ok(paths)
+    }
+
+    pub fn step(&mut self) {
+        self.ticks += 1;
+
+        let wrap = self.params.bounds().wrap_size();
+        self.grids
+            .fishes
+            .resize_with(self.fishes.len(), || SpatialGrid::new(GRID_CELL_SIZE));
+        for (grid, fishes) in self.grids.fishes.iter_mut().zip(&self.fishes) {
+            grid.rebuild(fishes, wrap);
+        }
+        self.process_plants(self.ticks.is_multiple_of(GROW_INTERVAL));
+
+        self.grids.plants.rebuild(&self.plants, wrap);
+        for index in 0..self.fishes.len() {
+            self.process_fishes(index);
+            self.grids.fishes[index].rebuild(&self.fishes[index], wrap);
+        }
+    }
+
+    fn check_proximity<O: Vision + Position, T: Position>(
+        origin: &O,
+        vec: &[T],
+        grid: &SpatialGrid,
+        bounds: &Bounds,
+    ) -> Option<(usize, Vector2)> {
+        let mut closest_tgt = None;
+        let mut min_dist = f64::MAX;
+        for i in grid.query(origin.pos(), origin.sight_radius()) {
+            let tgt_pos = bounds.image(origin.pos(), vec[i].pos());
+            let tgt_dist_sqr = origin.in_sight(tgt_pos);
+
+            if (tgt_dist_sqr > 0.0) && (tgt_dist_sqr < min_dist) {
+                min_dist = tgt_dist_sqr;
+                closest_tgt = Some((i, tgt_pos));
+            }
+        }
+
+        closest_tgt
+    }
+
+    fn nearest<O: Vision + Position, T: Position>(
+        origin: &O,
+        vec: &[T],
+        grid: &SpatialGrid,
+        bounds: &Bounds,
+    ) -> Option<Vector2> {
+        World::check_proximity(origin, vec, grid, bounds).map(|(_, pos)| pos)
+    }
+
+    fn closest_fish<O: Vision + Position>(
+        &self,
+        origin: &O,
+        species: &[usize],
+        bounds: &Bounds,
+    ) -> Option<(usize, usize, Vector2)> {
+        species
+            .iter()
+            .filter_map(|&s| {
+                World::check_proximity(origin, &self.fishes[s], &self.grids.fishes[s], bounds)
+                    .map(|(i, pos)| (s, i, pos))
+            })
+            .min_by(|a, b| {
+                let dist_a = (a.2 - origin.pos()).length_sqr();
+                let dist_b = (b.2 - origin.pos()).length_sqr();
+                dist_a.total_cmp(&dist_b)
+            })
+    }
+
+    fn sense_all(&self, index: usize) -> Vec<Option<Senses>> {
+        let bounds = self.params.bounds();
+        let diet = self.species.diet(index);
+        self.fishes[index]
+            .iter()
+            .map(|fish| {
+                fish.has_brain().then(|| Senses {
+                    plant: World::nearest(fish, &self.plants, &self.grids.plants, &bounds),
+                    predator: self
+                        .closest_fish(fish, &diet.predators, &bounds)
+                        .map(|(_, _, pos)| pos),
+                    prey: self
+                        .closest_fish(fish, &diet.prey, &bounds)
+                        .map(|(_, _, pos)| pos),
+                })
+            })
+            .collect()
+    }
+
+    fn gather_flock(fishes: &mut [Fish], grid: &SpatialGrid, bounds: &Bounds) {
+        for i in 0..fishes.len() {
+            let neighbors: Vec<(Vector2, Vector2)> = grid
+                .query(fishes[i].pos(), fishes[i].sight_radius())
+                .filter(|&j| j != i)
+                .map(|j| {
+                    (
+                        bounds.image(fishes[i].pos(), fishes[j].pos()),
+                        fishes[j].vel(),
+                    )
+                })
+                .filter(|&(pos, _)| fishes[i].in_sight(pos) > 0.0)
+                .collect();
+
+            for (pos, vel) in neighbors {
+                fishes[i].ponder_flock(pos, vel);
+            }
+            fishes[i].compute_flock();
+        }
+    }
+
+    fn process_plants(&mut self, do_grow: bool) {
+        let bounds = self.params.bounds();
+        let grazers: Vec<usize> = self.species.grazers().collect();
+        let mut i = self.plants.len();
+        while i != 0 {
+            i -= 1;
+            let closest_grazer = self.closest_fish(&self.plants[i], &grazers, &bounds);
+
+            match closest_grazer {
+                None => {
+                    if do_grow {
+                        let rootlings = self.plants[i].grow(&mut self.rng);
+                        match rootlings {
+                            None => {}
+                            Some((rootling_1, rootling_2)) => {
+                                for mut rootling in [rootling_1, rootling_2] {
+                                    if rootling.confine(&bounds)
+                                        && obstacles::escape_all(
+                                            &self.params.obstacles,
+                                            rootling.pos(),
+                                        )
+                                        .is_none()
+                                    {
+                                        self.plants.push(rootling);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                Some((index, grazer, _)) => {
+                    let species = self.species.get(index);
+                    let bite = species.bite.min(self.plants[i].mass() / 2.0);
+                    self.plants[i].bitten(bite);
+                    self.fishes[index][grazer].eat(bite, species.assimilation);
+
+                    self.plants[i].health -= 1;
+                    if self.plants[i].health < 3 {
+                        self.plants.swap_remove(i);
+                    }
+                }
+            }
+        }
+    }
+
+    fn process_fishes(&mut self, index: usize) {
+        let bounds = self.params.bounds();
+        World::gather_flock(&mut self.fishes[index], &self.grids.fishes[index], &bounds);
+        let senses = self.sense_all(index);
+        let species = self.species.get(index);
+        let diet = self.species.diet(index);
+        let reproduction = species.reproduction(
+            self.params.mutation_rate,
+            self.params.mutation_sigma,
+            self.params.brain_sigma,
+        );
+
+        let mut i = self.fishes[index].len();
+        while i != 0 {
+            i -= 1;
+            let fish = &self.fishes[index][i];
+            let closest_plant = if diet.plants {
+                World::check_proximity(fish, &self.plants, &self.grids.plants, &bounds)
+            } else {
+                None
+            };
+            let closest_prey = self
+                .closest_fish(fish, &diet.prey, &bounds)
+                .map(|(s, j, pos)| (pos, self.fishes[s][j].vel()));
+            let closest_predator = self
+                .closest_fish(fish, &diet.predators, &bounds)
+                .map(|(s, j, pos)| (s, j, pos, self.fishes[s][j].vel()));
+
+            let fish = &mut self.fishes[index][i];
+            fish.avoid(
+                &self.params.obstacles,
+                self.params.feeler_length,
+                self.params.avoid_w,
+            );
+            fish.contain(&bounds, self.params.avoid_w);
+            match &senses[i] {
+                Some(sensed) => {
+                    fish.think(sensed.plant, sensed.predator, sensed.prey, species.think_w)
+                }
+                None => {
+                    if let Some((_, _, predator_pos, predator_vel)) = closest_predator {
+                        fish.evade(predator_pos, predator_vel, species.flee_w);
+                    }
+                    if let Some((_, plant_pos)) = closest_plant {
+                        fish.arrive(plant_pos, species.arrive_w);
+                    }
+                    if let Some((prey_pos, prey_vel)) = closest_prey {
+                        fish.pursuit(prey_pos, prey_vel, species.pursuit_w);
+                    }
+                }
+            }
+            fish.flock(species.flock_w);
+            if senses[i].is_none()
+                && closest_predator.is_none()
+                && closest_plant.is_none()
+                && closest_prey.is_none()
+            {
+                fish.wander(species.wander_w, &mut self.rng);
+            }
+            fish.apply_steering(self.params.steering_mode);
+            fish.confine(&bounds);
+            fish.collide(&self.params.obstacles);
+
+            if let Some((predator_species, predator, _, _)) = closest_predator {
+                let predator_pos = self.fishes[predator_species][predator].pos();
+                if bounds
+                    .delta(self.fishes[index][i].pos(), predator_pos)
+                    .length()
+                    < 10.0
+                {
+                    let prey = self.fishes[index].swap_remove(i);
+                    let assimilation = self.species.get(predator_species).assimilation;
+                    self.fishes[predator_species][predator].eat(prey.mass(), assimilation);
+                    continue;
+                }
+            }
+
+            if !self.fishes[index][i].metabolise(species.basal_cost, species.swim_cost) {
+                self.fishes[index].swap_remove(i);
+                continue;
+            }
+
+            let litter = self.fishes[index][i].reproduce(&reproduction, &mut self.rng);
+            for mut child in litter {
+                child.confine(&bounds);
+                if self.placeable(child.pos()).is_some() {
+                    self.fishes[index].push(child);
+                }
+            }
+        }
+    }
+}